@@ -0,0 +1,17 @@
+//! List the chats a Telegram bot has seen recently.
+//!
+//! Use one of the IDs as `--notification-telegram-target-chat`.
+
+use clap::Parser;
+
+#[derive(Parser)]
+pub struct Cli {
+    #[command(flatten)]
+    pub chats: pling::clap::TelegramChats,
+}
+
+fn main() -> anyhow::Result<()> {
+    let matches = Cli::parse();
+    println!("{}", matches.chats.seen_chats_ureq()?);
+    Ok(())
+}
@@ -14,7 +14,7 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-#![allow(clippy::struct_field_names)]
+#![allow(clippy::struct_excessive_bools, clippy::struct_field_names)]
 
 use url::Url;
 
@@ -52,10 +52,13 @@ impl Args {
                 .send_ureq(text)
                 .context("Failed to send Slack notification")?;
         }
-        if let Some(notifier) = self.telegram.to_plain() {
+        if let Some(notifier) = self.slack.to_bot() {
             notifier
                 .send_ureq(text)
-                .context("Failed to send Telegram notification")?;
+                .context("Failed to send Slack notification")?;
+        }
+        if let Some(notifier) = self.telegram.to_plain() {
+            check_telegram_results(notifier.send_ureq(text))?;
         }
         if let Some(notifier) = &self.webhook.to_plain() {
             notifier
@@ -86,11 +89,14 @@ impl Args {
                 .await
                 .context("Failed to send Slack notification")?;
         }
-        if let Some(notifier) = self.telegram.to_plain() {
+        if let Some(notifier) = self.slack.to_bot() {
             notifier
                 .send_reqwest(text)
                 .await
-                .context("Failed to send Telegram notification")?;
+                .context("Failed to send Slack notification")?;
+        }
+        if let Some(notifier) = self.telegram.to_plain() {
+            check_telegram_results(notifier.send_reqwest(text).await)?;
         }
         if let Some(notifier) = &self.webhook.to_plain() {
             notifier
@@ -102,6 +108,38 @@ impl Args {
     }
 }
 
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+fn check_telegram_results(results: Vec<anyhow::Result<crate::TelegramSent>>) -> anyhow::Result<()> {
+    use std::fmt::Write as _;
+
+    let total = results.len();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(sent) => {
+                if let Some(chat_id) = sent.migrated_to_chat_id {
+                    eprintln!(
+                        "Warning: The Telegram group {} was upgraded to a supergroup. Use {chat_id} as --notification-telegram-target-chat from now on.",
+                        sent.target_chat
+                    );
+                }
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let mut message = format!(
+        "Failed to send Telegram notification to {} of {total} chats",
+        errors.len()
+    );
+    for error in errors {
+        write!(message, "\n- {error:#}").expect("writing to a String should not fail");
+    }
+    Err(anyhow::anyhow!(message))
+}
+
 #[derive(clap::Args)]
 pub struct Matrix {
     #[arg(
@@ -168,13 +206,142 @@ pub struct Slack {
         help_heading = "Notification Options",
     )]
     pub notification_slack_webhook: Option<Url>,
+
+    /// Slack Bot User OAuth Token (xoxb-…) to post via the Web API.
+    ///
+    /// The app needs the chat:write scope and the bot has to be a member of the channel.
+    #[arg(
+        long,
+        env,
+        hide_env_values = true,
+        value_hint = clap::ValueHint::Other,
+        value_name = "TOKEN",
+        requires = "notification_slack_channel",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_bot_token: Option<String>,
+
+    /// Slack channel ID or name to post to with the bot token.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "CHANNEL",
+        requires = "notification_slack_bot_token",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_channel: Option<String>,
+
+    /// Slack channel like #alerts to post webhook messages to.
+    ///
+    /// Only legacy webhooks of custom integrations can change the channel.
+    /// Webhooks of Slack apps always post to the channel they were created for.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "CHANNEL",
+        requires = "notification_slack_webhook",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_webhook_channel: Option<String>,
+
+    /// Name shown as the sender of webhook messages instead of the app name.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "NAME",
+        requires = "notification_slack_webhook",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_username: Option<String>,
+
+    /// Emoji like `:robot_face:` shown as the sender icon of webhook messages.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "EMOJI",
+        requires = "notification_slack_webhook",
+        conflicts_with = "notification_slack_icon_url",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_icon_emoji: Option<String>,
+
+    /// Image URL shown as the sender icon of webhook messages.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Url,
+        value_name = "URL",
+        requires = "notification_slack_webhook",
+        help_heading = "Notification Options",
+    )]
+    pub notification_slack_icon_url: Option<Url>,
+
+    /// Do not show previews of text-based links in webhook messages.
+    #[arg(
+        long,
+        env,
+        requires = "notification_slack_webhook",
+        help_heading = "Notification Options"
+    )]
+    pub notification_slack_no_unfurl_links: bool,
+
+    /// Do not show previews of media links in webhook messages.
+    #[arg(
+        long,
+        env,
+        requires = "notification_slack_webhook",
+        help_heading = "Notification Options"
+    )]
+    pub notification_slack_no_unfurl_media: bool,
 }
 impl Slack {
     #[must_use]
     pub fn to_plain(&self) -> Option<crate::Slack> {
         self.notification_slack_webhook
             .clone()
-            .map(|webhook| crate::Slack { webhook })
+            .map(|webhook| crate::Slack {
+                webhook,
+                options: self.message_options(),
+            })
+    }
+
+    fn message_options(&self) -> crate::SlackMessageOptions {
+        let mut options = crate::SlackMessageOptions::default();
+        if let Some(channel) = &self.notification_slack_webhook_channel {
+            options = options.channel(channel);
+        }
+        if let Some(username) = &self.notification_slack_username {
+            options = options.username(username);
+        }
+        if let Some(emoji) = &self.notification_slack_icon_emoji {
+            options = options.icon_emoji(emoji);
+        }
+        if let Some(url) = &self.notification_slack_icon_url {
+            options = options.icon_url(url.clone());
+        }
+        if self.notification_slack_no_unfurl_links {
+            options = options.unfurl_links(false);
+        }
+        if self.notification_slack_no_unfurl_media {
+            options = options.unfurl_media(false);
+        }
+        options
+    }
+
+    #[must_use]
+    pub fn to_bot(&self) -> Option<crate::SlackBot> {
+        if let (Some(token), Some(channel)) = (
+            &self.notification_slack_bot_token,
+            &self.notification_slack_channel,
+        ) {
+            Some(crate::SlackBot::new(token.clone(), channel.clone()))
+        } else {
+            None
+        }
     }
 }
 
@@ -190,20 +357,35 @@ pub struct Telegram {
         requires = "notification_telegram_target_chat",
         help_heading = "Notification Options",
     )]
-    pub notification_telegram_bot_token: Option<String>,
+    pub notification_telegram_bot_token: Option<crate::TelegramBotToken>,
 
     /// Chat/User ID or Chat/Channel Username.
     ///
     /// Make sure the bot is added to the chat/channel.
+    /// Multiple chats can be given comma-separated or by repeating the argument.
     #[arg(
         long,
         env,
         value_hint = clap::ValueHint::Other,
         value_name = "ID/USERNAME",
+        value_delimiter = ',',
         requires = "notification_telegram_bot_token",
         help_heading = "Notification Options",
     )]
-    pub notification_telegram_target_chat: Option<crate::TelegramTargetChat>,
+    pub notification_telegram_target_chat: Vec<crate::TelegramTargetChat>,
+
+    /// Telegram Bot API server.
+    ///
+    /// Only needed for a self-hosted Bot API server.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Url,
+        value_name = "URL",
+        default_value = crate::TELEGRAM_DEFAULT_API_URL,
+        help_heading = "Notification Options",
+    )]
+    pub notification_telegram_api_url: Url,
 
     #[arg(
         long,
@@ -213,6 +395,47 @@ pub struct Telegram {
     )]
     pub notification_telegram_disable_web_page_preview: bool,
 
+    /// URL to use for the link preview.
+    ///
+    /// When not set the first URL found in the message text will be used.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Url,
+        value_name = "URL",
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options",
+    )]
+    pub notification_telegram_link_preview_url: Option<Url>,
+
+    /// Shrink the media in the link preview.
+    #[arg(
+        long,
+        env,
+        requires = "notification_telegram_bot_token",
+        conflicts_with = "notification_telegram_link_preview_prefer_large_media",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_link_preview_prefer_small_media: bool,
+
+    /// Enlarge the media in the link preview.
+    #[arg(
+        long,
+        env,
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_link_preview_prefer_large_media: bool,
+
+    /// Show the link preview above the message text.
+    #[arg(
+        long,
+        env,
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_link_preview_above_text: bool,
+
     /// Sends the message silently.
     ///
     /// Users will receive a notification with no sound.
@@ -223,20 +446,124 @@ pub struct Telegram {
         help_heading = "Notification Options"
     )]
     pub notification_telegram_silent: bool,
+
+    /// What to do with texts longer than 4096 characters.
+    #[arg(
+        long,
+        env,
+        value_enum,
+        default_value_t,
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_long_text: crate::TelegramLongText,
+
+    /// Format the text with HTML or Markdown V2.
+    ///
+    /// The text is then interpreted as formatting, so characters which should be shown as they are need to be escaped.
+    /// For HTML these are `<`, `>` and `&` as `&lt;`, `&gt;` and `&amp;`.
+    /// For Markdown V2 these are ``_*[]()~`>#+-=|{}.!`` and `\` which need a preceding `\`.
+    /// When not set the text is sent as plain text.
+    #[arg(
+        long,
+        env,
+        value_enum,
+        value_name = "MODE",
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_parse_mode: Option<crate::TelegramParseMode>,
+
+    /// Send the message to this topic of a forum supergroup.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "ID",
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_thread_id: Option<i64>,
+
+    /// Protect the message from forwarding and saving.
+    #[arg(
+        long,
+        env,
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_protect_content: bool,
+
+    /// Ignore the broadcasting limits for a fee paid in Telegram Stars.
+    #[arg(
+        long,
+        env,
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_allow_paid_broadcast: bool,
+
+    /// Send the message on behalf of this business connection.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "ID",
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_business_connection_id: Option<String>,
+
+    /// Add this message effect to the message.
+    ///
+    /// Only works in private chats.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Other,
+        value_name = "ID",
+        requires = "notification_telegram_bot_token",
+        help_heading = "Notification Options"
+    )]
+    pub notification_telegram_message_effect_id: Option<String>,
 }
 impl Telegram {
+    #[must_use]
+    fn link_preview_options(&self) -> Option<crate::TelegramLinkPreviewOptions> {
+        let options = crate::TelegramLinkPreviewOptions {
+            is_disabled: false,
+            url: self.notification_telegram_link_preview_url.clone(),
+            prefer_small_media: self.notification_telegram_link_preview_prefer_small_media,
+            prefer_large_media: self.notification_telegram_link_preview_prefer_large_media,
+            show_above_text: self.notification_telegram_link_preview_above_text,
+        };
+        (options != crate::TelegramLinkPreviewOptions::default()).then_some(options)
+    }
+
     #[must_use]
     pub fn to_plain(&self) -> Option<crate::Telegram> {
-        if let (Some(bot_token), Some(target_chat)) = (
+        if let (Some(bot_token), false) = (
             &self.notification_telegram_bot_token,
-            &self.notification_telegram_target_chat,
+            self.notification_telegram_target_chat.is_empty(),
         ) {
             Some(crate::Telegram {
-                bot_token: bot_token.to_owned(),
-                target_chat: target_chat.clone(),
+                bot_token: bot_token.clone(),
+                target_chats: self.notification_telegram_target_chat.clone(),
+                api_url: self.notification_telegram_api_url.clone(),
                 disable_web_page_preview: self.notification_telegram_disable_web_page_preview,
+                link_preview_options: self.link_preview_options(),
                 disable_notification: self.notification_telegram_silent,
-                parse_mode: None,
+                parse_mode: self.notification_telegram_parse_mode,
+                long_text: self.notification_telegram_long_text,
+                message_options: crate::TelegramMessageOptions {
+                    business_connection_id: self
+                        .notification_telegram_business_connection_id
+                        .clone(),
+                    message_thread_id: self.notification_telegram_thread_id,
+                    protect_content: self.notification_telegram_protect_content,
+                    allow_paid_broadcast: self.notification_telegram_allow_paid_broadcast,
+                    message_effect_id: self.notification_telegram_message_effect_id.clone(),
+                },
             })
         } else {
             None
@@ -244,6 +571,76 @@ impl Telegram {
     }
 }
 
+/// List the chats a Telegram bot has seen recently.
+///
+/// Helps to find the value for `--notification-telegram-target-chat`.
+///
+/// ```no_run
+/// use clap::Parser;
+///
+/// #[derive(Parser)]
+/// pub struct Cli {
+///     #[command(flatten)]
+///     pub chats: pling::clap::TelegramChats,
+/// }
+///
+/// let matches = Cli::parse();
+/// println!("{}", matches.chats.seen_chats_ureq()?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(clap::Args)]
+pub struct TelegramChats {
+    /// Bot Token from `@BotFather` in Telegram
+    #[arg(
+        long,
+        env,
+        hide_env_values = true,
+        value_hint = clap::ValueHint::Other,
+        value_name = "BOT_TOKEN",
+    )]
+    pub notification_telegram_bot_token: crate::TelegramBotToken,
+
+    /// Telegram Bot API server.
+    ///
+    /// Only needed for a self-hosted Bot API server.
+    #[arg(
+        long,
+        env,
+        value_hint = clap::ValueHint::Url,
+        value_name = "URL",
+        default_value = crate::TELEGRAM_DEFAULT_API_URL,
+    )]
+    pub notification_telegram_api_url: Url,
+}
+impl TelegramChats {
+    /// Get the bot and the chats it has seen recently via [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// Check [`crate::Telegram::seen_chats_ureq`] for more details.
+    #[cfg(feature = "ureq")]
+    pub fn seen_chats_ureq(&self) -> anyhow::Result<crate::TelegramSeenChats> {
+        crate::Telegram::seen_chats_ureq(
+            &self.notification_telegram_api_url,
+            &self.notification_telegram_bot_token,
+        )
+    }
+
+    /// Get the bot and the chats it has seen recently via [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// Check [`crate::Telegram::seen_chats_reqwest`] for more details.
+    #[cfg(feature = "reqwest")]
+    pub async fn seen_chats_reqwest(&self) -> anyhow::Result<crate::TelegramSeenChats> {
+        crate::Telegram::seen_chats_reqwest(
+            &self.notification_telegram_api_url,
+            &self.notification_telegram_bot_token,
+        )
+        .await
+    }
+}
+
 #[derive(clap::Args)]
 pub struct Webhook {
     /// Send a POST request to the given URL.
@@ -280,3 +677,122 @@ fn verify_command() {
 
     Cli::command().debug_assert();
 }
+
+#[test]
+fn verify_telegram_chats_command() {
+    use clap::{CommandFactory as _, Parser};
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        pub chats: TelegramChats,
+    }
+
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn telegram_multiple_target_chats() {
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        pub telegram: Telegram,
+    }
+
+    let cli = Cli::try_parse_from([
+        "pling",
+        "--notification-telegram-bot-token=123456:ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi",
+        "--notification-telegram-target-chat=-100123,@channel",
+        "--notification-telegram-target-chat=42",
+    ])
+    .unwrap();
+    let telegram = cli.telegram.to_plain().unwrap();
+    assert_eq!(
+        telegram.target_chats,
+        [
+            crate::TelegramTargetChat::Id(-100_123),
+            crate::TelegramTargetChat::Username("@channel".to_owned()),
+            crate::TelegramTargetChat::Id(42),
+        ]
+    );
+}
+
+#[test]
+fn telegram_invalid_bot_token() {
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        pub telegram: Telegram,
+    }
+
+    let error = Cli::try_parse_from([
+        "pling",
+        "--notification-telegram-bot-token=123:ABC",
+        "--notification-telegram-target-chat=42",
+    ])
+    .err()
+    .unwrap();
+    assert!(error.to_string().contains("35 characters"));
+}
+
+#[test]
+fn telegram_parse_mode() {
+    use clap::{CommandFactory as _, Parser};
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        pub telegram: Telegram,
+    }
+
+    let cli = Cli::try_parse_from([
+        "pling",
+        "--notification-telegram-bot-token=123456:ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi",
+        "--notification-telegram-target-chat=42",
+        "--notification-telegram-parse-mode=markdown-v2",
+    ])
+    .unwrap();
+    let telegram = cli.telegram.to_plain().unwrap();
+    assert_eq!(
+        telegram.parse_mode,
+        Some(crate::TelegramParseMode::MarkdownV2)
+    );
+
+    let help = Cli::command().render_long_help().to_string();
+    assert!(help.contains("html, markdown-v2"));
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[test]
+fn telegram_errors_are_returned() {
+    let sent = crate::TelegramSent {
+        target_chat: crate::TelegramTargetChat::Id(1),
+        messages: Vec::new(),
+        migrated_to_chat_id: None,
+    };
+    let results = vec![
+        Ok(sent),
+        Err(anyhow::anyhow!("Forbidden").context("Failed to send to Telegram chat 2")),
+        Err(anyhow::anyhow!("Not Found").context("Failed to send to Telegram chat 3")),
+    ];
+    let error = check_telegram_results(results).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Failed to send Telegram notification to 2 of 3 chats\n- Failed to send to Telegram chat 2: Forbidden\n- Failed to send to Telegram chat 3: Not Found"
+    );
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[test]
+fn telegram_migration_is_not_an_error() {
+    let sent = crate::TelegramSent {
+        target_chat: crate::TelegramTargetChat::Id(-123),
+        messages: Vec::new(),
+        migrated_to_chat_id: Some(-100_123),
+    };
+    check_telegram_results(vec![Ok(sent)]).unwrap();
+}
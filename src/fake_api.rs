@@ -0,0 +1,114 @@
+//! Minimal local fake of the Telegram Bot API or Slack Web API to test requests against
+
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Last path segment which is the API method like `sendMessage` or `chat.postMessage`
+    pub method: String,
+    pub authorization: Option<String>,
+    pub body: String,
+    /// The body parsed as `application/x-www-form-urlencoded`
+    pub form: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).unwrap()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.form
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct FakeApi {
+    pub url: Url,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeApi {
+    /// Start the fake server which answers every request with the JSON body returned by `respond`.
+    pub fn start<F>(respond: F) -> Self
+    where
+        F: Fn(&Request) -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        std::thread::spawn({
+            let requests = Arc::clone(&requests);
+            move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else { break };
+                    handle(stream, &respond, &requests);
+                }
+            }
+        });
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle<F>(mut stream: TcpStream, respond: &F, requests: &Mutex<Vec<Request>>)
+where
+    F: Fn(&Request) -> String,
+{
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let method = request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default()
+        .to_owned();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line.trim().is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        match name.to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap(),
+            "authorization" => authorization = Some(value.trim().to_owned()),
+            _ => {}
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    let request = Request {
+        method,
+        authorization,
+        form: url::form_urlencoded::parse(&body).into_owned().collect(),
+        body: String::from_utf8(body).unwrap(),
+    };
+    let response = respond(&request);
+    requests.lock().unwrap().push(request);
+
+    let status = serde_json::from_str::<serde_json::Value>(&response)
+        .ok()
+        .and_then(|value| value.get("error_code")?.as_u64())
+        .unwrap_or(200);
+    write!(
+        stream,
+        "HTTP/1.1 {status} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )
+    .unwrap();
+}
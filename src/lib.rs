@@ -6,15 +6,36 @@
 #![cfg_attr(not(any(feature = "reqwest", feature = "ureq")), allow(dead_code))]
 
 pub use crate::matrix::Matrix;
-pub use crate::slack::Slack;
+pub use crate::slack::{
+    ApiError as SlackApiError, Attachment as SlackAttachment, Block as SlackBlock, Bot as SlackBot,
+    Button as SlackButton, ButtonStyle as SlackButtonStyle, Field as SlackField, File as SlackFile,
+    Icon as SlackIcon, Message as SlackMessage, MessageOptions as SlackMessageOptions,
+    Mrkdwn as SlackMrkdwn, Posted as SlackPosted, Scheduled as SlackScheduled,
+    Severity as SlackSeverity, Slack, Text as SlackText, Thread as SlackThread,
+    Uploaded as SlackUploaded, User as SlackUser, WebhookError as SlackWebhookError,
+    DEFAULT_API_URL as SLACK_DEFAULT_API_URL,
+};
 pub use crate::telegram::{
-    ParseMode as TelegramParseMode, TargetChat as TelegramTargetChat, Telegram,
+    ApiError as TelegramApiError, BotToken as TelegramBotToken, Chat as TelegramChat,
+    ChatAccess as TelegramChatAccess, ChatType as TelegramChatType, Command as TelegramCommand,
+    EntityKind as TelegramEntityKind, EntityText as TelegramEntityText,
+    Incoming as TelegramIncoming, LinkPreviewOptions as TelegramLinkPreviewOptions,
+    Location as TelegramLocation, LongText as TelegramLongText, Message as TelegramMessage,
+    MessageEntity as TelegramMessageEntity, MessageOptions as TelegramMessageOptions,
+    ParseMode as TelegramParseMode, Poll as TelegramPoll,
+    ResponseParameters as TelegramResponseParameters, SeenChats as TelegramSeenChats,
+    Sent as TelegramSent, TargetChat as TelegramTargetChat, Telegram,
+    TextBuilder as TelegramTextBuilder, User as TelegramUser, Venue as TelegramVenue,
+    Verification as TelegramVerification, DEFAULT_API_URL as TELEGRAM_DEFAULT_API_URL,
+    MAX_TEXT_LENGTH as TELEGRAM_MAX_TEXT_LENGTH,
 };
 pub use crate::webhook::Webhook;
 
 #[cfg(feature = "clap")]
 pub mod clap;
 
+#[cfg(all(test, feature = "ureq"))]
+mod fake_api;
 mod matrix;
 mod slack;
 mod telegram;
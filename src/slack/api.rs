@@ -0,0 +1,253 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize as _;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use url::Url;
+
+/// Error returned by the Slack Web API
+///
+/// Slack responds with HTTP status 200 and `{"ok":false,"error":"…"}` on failures.
+///
+/// Documentation: <https://api.slack.com/web#evaluating_responses>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// Machine readable error like `channel_not_found` or `not_in_channel`
+    pub error: String,
+
+    /// Further details like which block is invalid
+    pub messages: Vec<String>,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "Slack API error: {}", self.error)?;
+        if !self.messages.is_empty() {
+            write!(fmt, " ({})", self.messages.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[derive(Default, serde::Deserialize)]
+struct ResponseMetadata {
+    #[serde(default)]
+    messages: Vec<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Status {
+    ok: bool,
+    #[serde(default)]
+    error: String,
+    #[serde(default)]
+    response_metadata: ResponseMetadata,
+}
+
+fn parse_response<T: DeserializeOwned>(status: u16, body: &str) -> anyhow::Result<T> {
+    use anyhow::Context as _;
+
+    let value = serde_json::from_str::<serde_json::Value>(body).with_context(|| {
+        format!("Slack API responded with HTTP status {status} and an unexpected body")
+    })?;
+    let response = Status::deserialize(&value).with_context(|| {
+        format!("Slack API responded with HTTP status {status} and an unexpected body")
+    })?;
+    if response.ok {
+        serde_json::from_value(value).context("Slack API response has an unexpected format")
+    } else {
+        Err(ApiError {
+            error: response.error,
+            messages: response.response_metadata.messages,
+        }
+        .into())
+    }
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const JSON: &str = "application/json; charset=utf-8";
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const FORM: &str = "application/x-www-form-urlencoded";
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[must_use]
+fn encode_form(form: &[(&str, String)]) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(form)
+        .finish()
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[must_use]
+fn generate_url(api_url: &Url, method: &str) -> String {
+    let api_url = api_url.as_str().trim_end_matches('/');
+    format!("{api_url}/{method}")
+}
+
+/// Call a Web API method with a JSON body via [`ureq`] and parse its response.
+#[cfg(feature = "ureq")]
+pub fn call_ureq<T: DeserializeOwned>(
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    body: &impl serde::Serialize,
+) -> anyhow::Result<T> {
+    let body = serde_json::to_string(body)?;
+    send_ureq(api_url, token, method, JSON, body)
+}
+
+/// Call a Web API method with a form body via [`ureq`] and parse its response.
+///
+/// Some methods like `files.getUploadURLExternal` do not accept JSON.
+#[cfg(feature = "ureq")]
+pub fn call_form_ureq<T: DeserializeOwned>(
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    form: &[(&str, String)],
+) -> anyhow::Result<T> {
+    send_ureq(api_url, token, method, FORM, encode_form(form))
+}
+
+#[cfg(feature = "ureq")]
+fn send_ureq<T: DeserializeOwned>(
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    content_type: &str,
+    body: String,
+) -> anyhow::Result<T> {
+    let mut response = ureq::post(&generate_url(api_url, method))
+        .header(ureq::http::header::USER_AGENT, crate::USER_AGENT_UREQ)
+        .header(
+            ureq::http::header::AUTHORIZATION,
+            &format!("Bearer {token}"),
+        )
+        .header(ureq::http::header::CONTENT_TYPE, content_type)
+        .config()
+        .http_status_as_error(false)
+        .build()
+        .send(body)?;
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string()?;
+    parse_response(status, &body)
+}
+
+/// Upload the raw file content to the URL returned by `files.getUploadURLExternal` via [`ureq`].
+#[cfg(feature = "ureq")]
+pub fn upload_ureq(upload_url: &str, content: &[u8]) -> anyhow::Result<()> {
+    ureq::post(upload_url)
+        .header(ureq::http::header::USER_AGENT, crate::USER_AGENT_UREQ)
+        .header(ureq::http::header::CONTENT_TYPE, "application/octet-stream")
+        .send(content)?;
+    Ok(())
+}
+
+#[cfg(feature = "reqwest")]
+pub fn reqwest_client() -> reqwest::Result<reqwest::Client> {
+    reqwest::ClientBuilder::new()
+        .user_agent(crate::USER_AGENT_REQWEST)
+        .build()
+}
+
+/// Call a Web API method with a JSON body via [`reqwest`] and parse its response.
+#[cfg(feature = "reqwest")]
+pub async fn call_reqwest<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    body: &(impl serde::Serialize + Sync),
+) -> anyhow::Result<T> {
+    let body = serde_json::to_string(body)?;
+    send_reqwest(client, api_url, token, method, JSON, body).await
+}
+
+/// Call a Web API method with a form body via [`reqwest`] and parse its response.
+///
+/// Some methods like `files.getUploadURLExternal` do not accept JSON.
+#[cfg(feature = "reqwest")]
+pub async fn call_form_reqwest<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    form: &[(&str, String)],
+) -> anyhow::Result<T> {
+    send_reqwest(client, api_url, token, method, FORM, encode_form(form)).await
+}
+
+#[cfg(feature = "reqwest")]
+async fn send_reqwest<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_url: &Url,
+    token: &str,
+    method: &str,
+    content_type: &'static str,
+    body: String,
+) -> anyhow::Result<T> {
+    let response = client
+        .post(generate_url(api_url, method))
+        .bearer_auth(token)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(body)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+    let status = response.status().as_u16();
+    let body = response.text().await.map_err(reqwest::Error::without_url)?;
+    parse_response(status, &body)
+}
+
+/// Upload the raw file content to the URL returned by `files.getUploadURLExternal` via [`reqwest`].
+#[cfg(feature = "reqwest")]
+pub async fn upload_reqwest(
+    client: &reqwest::Client,
+    upload_url: &str,
+    content: &[u8],
+) -> anyhow::Result<()> {
+    client
+        .post(upload_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .body(content.to_vec())
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(reqwest::Error::without_url)?;
+    Ok(())
+}
+
+#[test]
+fn parse_ok() {
+    #[derive(serde::Deserialize)]
+    struct Posted {
+        ts: String,
+    }
+    let result = parse_response::<Posted>(
+        200,
+        r#"{"ok":true,"channel":"C123","ts":"1503435956.000247"}"#,
+    )
+    .unwrap();
+    assert_eq!(result.ts, "1503435956.000247");
+}
+
+#[test]
+fn parse_api_error() {
+    let body = r#"{"ok":false,"error":"invalid_blocks","response_metadata":{"messages":["[ERROR] must be more than 0 characters [json-pointer:/blocks/0/text/text]"]}}"#;
+    let error = parse_response::<bool>(200, body).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Slack API error: invalid_blocks ([ERROR] must be more than 0 characters [json-pointer:/blocks/0/text/text])"
+    );
+    let error = error.downcast::<ApiError>().unwrap();
+    assert_eq!(error.error, "invalid_blocks");
+}
+
+#[test]
+fn parse_unexpected_body() {
+    let error = parse_response::<bool>(502, "Bad Gateway").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Slack API responded with HTTP status 502 and an unexpected body"
+    );
+}
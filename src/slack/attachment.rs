@@ -0,0 +1,142 @@
+use url::Url;
+
+/// Legacy secondary content of a Slack message with a colored bar on its side
+///
+/// Documentation: <https://api.slack.com/reference/messaging/attachments>
+///
+/// ```
+/// use pling::{SlackAttachment, SlackSeverity};
+///
+/// let attachment = SlackAttachment::new(SlackSeverity::Danger)
+///     .title("Disk full")
+///     .field("Host", "db-1", true)
+///     .field("Usage", "100%", true);
+/// assert_eq!(attachment.color.as_deref(), Some("danger"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[must_use]
+pub struct Attachment {
+    /// `good`, `warning`, `danger` or a hex color like `#439FE0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Makes the title a link
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_link: Option<Url>,
+
+    /// Formatted with Slack's own `mrkdwn` syntax
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+
+    /// Small text at the bottom
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+}
+
+/// Key/value pair shown in a table of an [`Attachment`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Field {
+    pub title: String,
+    pub value: String,
+
+    /// Short fields are shown side by side
+    pub short: bool,
+}
+
+/// How severe the content of an [`Attachment`] is. Each severity has its own color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Blue
+    Info,
+    /// Green
+    Good,
+    /// Yellow
+    Warning,
+    /// Red
+    Danger,
+}
+
+impl Severity {
+    #[must_use]
+    pub const fn color(self) -> &'static str {
+        match self {
+            Self::Info => "#439FE0",
+            Self::Good => "good",
+            Self::Warning => "warning",
+            Self::Danger => "danger",
+        }
+    }
+}
+
+impl Attachment {
+    /// Attachment with the color of the severity
+    pub fn new(severity: Severity) -> Self {
+        Self {
+            color: Some(severity.color().to_owned()),
+            ..Self::default()
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+
+    pub fn title_link(mut self, url: Url) -> Self {
+        self.title_link = Some(url);
+        self
+    }
+
+    pub fn text(mut self, mrkdwn: &str) -> Self {
+        self.text = Some(mrkdwn.to_owned());
+        self
+    }
+
+    pub fn field(mut self, title: &str, value: &str, short: bool) -> Self {
+        self.fields.push(Field {
+            title: title.to_owned(),
+            value: value.to_owned(),
+            short,
+        });
+        self
+    }
+
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = Some(footer.to_owned());
+        self
+    }
+}
+
+#[test]
+fn json_default_is_empty_object() {
+    let json = serde_json::to_string(&Attachment::default()).unwrap();
+    assert_eq!(json, "{}");
+}
+
+#[test]
+fn json_full() {
+    let attachment = Attachment::new(Severity::Warning)
+        .title("CPU high")
+        .title_link(Url::parse("https://example.com/cpu").unwrap())
+        .text("Load is *95%*")
+        .field("Host", "web-1", true)
+        .footer("monitoring");
+    let json = serde_json::to_string(&attachment).unwrap();
+    assert_eq!(
+        json,
+        r#"{"color":"warning","title":"CPU high","title_link":"https://example.com/cpu","text":"Load is *95%*","fields":[{"title":"Host","value":"web-1","short":true}],"footer":"monitoring"}"#
+    );
+}
+
+#[test]
+fn severity_colors() {
+    assert_eq!(Severity::Info.color(), "#439FE0");
+    assert_eq!(Severity::Good.color(), "good");
+    assert_eq!(Severity::Danger.color(), "danger");
+}
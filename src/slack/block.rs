@@ -0,0 +1,224 @@
+use url::Url;
+
+use super::mrkdwn::Mrkdwn;
+
+/// Layout block of a Slack message
+///
+/// Documentation: <https://api.slack.com/reference/block-kit/blocks>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    /// Large bold text. Only [`Text::Plain`] is allowed.
+    Header {
+        text: Text,
+    },
+
+    /// Text with optional key/value fields shown in two columns
+    Section {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<Text>,
+        /// Up to 10 fields
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        fields: Vec<Text>,
+    },
+
+    /// Small grey text like timestamps or hints
+    Context {
+        elements: Vec<Text>,
+    },
+
+    Divider,
+
+    /// Up to 25 buttons
+    Actions {
+        elements: Vec<Button>,
+    },
+
+    Image {
+        image_url: Url,
+        /// Description of the image for screen readers
+        alt_text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<Text>,
+    },
+}
+
+impl Block {
+    #[must_use]
+    pub fn header(text: &str) -> Self {
+        Self::Header {
+            text: Text::plain(text),
+        }
+    }
+
+    /// Section with `mrkdwn` formatted text
+    #[must_use]
+    pub fn section(mrkdwn: &str) -> Self {
+        Self::Section {
+            text: Some(Text::mrkdwn(mrkdwn)),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Section with key/value fields. The key is shown in bold above the value.
+    ///
+    /// Keys and values are plain text which is escaped with [`Mrkdwn`].
+    #[must_use]
+    pub fn fields<'a, I>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let fields = fields
+            .into_iter()
+            .map(|(key, value)| {
+                let mrkdwn = Mrkdwn::new().bold(key).text("\n").text(value);
+                Text::mrkdwn(&mrkdwn.build())
+            })
+            .collect();
+        Self::Section { text: None, fields }
+    }
+
+    /// Context line with `mrkdwn` formatted text
+    #[must_use]
+    pub fn context(mrkdwn: &str) -> Self {
+        Self::Context {
+            elements: vec![Text::mrkdwn(mrkdwn)],
+        }
+    }
+
+    #[must_use]
+    pub const fn actions(buttons: Vec<Button>) -> Self {
+        Self::Actions { elements: buttons }
+    }
+
+    #[must_use]
+    pub fn image(image_url: Url, alt_text: &str) -> Self {
+        Self::Image {
+            image_url,
+            alt_text: alt_text.to_owned(),
+            title: None,
+        }
+    }
+}
+
+/// Text object
+///
+/// Documentation: <https://api.slack.com/reference/block-kit/composition-objects#text>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum Text {
+    #[serde(rename = "plain_text")]
+    Plain { text: String },
+    /// Formatted with Slack's own `mrkdwn` syntax
+    #[serde(rename = "mrkdwn")]
+    Mrkdwn { text: String },
+}
+
+impl Text {
+    #[must_use]
+    pub fn plain(text: &str) -> Self {
+        Self::Plain {
+            text: text.to_owned(),
+        }
+    }
+
+    #[must_use]
+    pub fn mrkdwn(text: &str) -> Self {
+        Self::Mrkdwn {
+            text: text.to_owned(),
+        }
+    }
+}
+
+/// Button opening a URL in the browser
+///
+/// Documentation: <https://api.slack.com/reference/block-kit/block-elements#button>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename = "button")]
+pub struct Button {
+    /// Only [`Text::Plain`] is allowed
+    pub text: Text,
+    pub url: Url,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ButtonStyle>,
+}
+
+impl Button {
+    #[must_use]
+    pub fn link(text: &str, url: Url) -> Self {
+        Self {
+            text: Text::plain(text),
+            url,
+            style: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ButtonStyle {
+    /// Green button
+    Primary,
+    /// Red button
+    Danger,
+}
+
+#[test]
+fn header_json() {
+    let json = serde_json::to_string(&Block::header("Build failed")).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"header","text":{"type":"plain_text","text":"Build failed"}}"#
+    );
+}
+
+#[test]
+fn fields_json() {
+    let json =
+        serde_json::to_string(&Block::fields([("Service", "api"), ("Status", "down")])).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"section","fields":[{"type":"mrkdwn","text":"*Service*\napi"},{"type":"mrkdwn","text":"*Status*\ndown"}]}"#
+    );
+}
+
+#[test]
+fn fields_are_escaped() {
+    let json = serde_json::to_string(&Block::fields([("a*b", "<!channel> & co")])).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"section","fields":[{"type":"mrkdwn","text":"*a∗b*\n&lt;!channel&gt; &amp; co"}]}"#
+    );
+}
+
+#[test]
+fn divider_json() {
+    let json = serde_json::to_string(&Block::Divider).unwrap();
+    assert_eq!(json, r#"{"type":"divider"}"#);
+}
+
+#[test]
+fn actions_json() {
+    let button = Button {
+        style: Some(ButtonStyle::Primary),
+        ..Button::link("Dashboard", Url::parse("https://example.com/").unwrap())
+    };
+    let json = serde_json::to_string(&Block::actions(vec![button])).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"actions","elements":[{"type":"button","text":{"type":"plain_text","text":"Dashboard"},"url":"https://example.com/","style":"primary"}]}"#
+    );
+}
+
+#[test]
+fn image_json() {
+    let block = Block::image(
+        Url::parse("https://example.com/graph.png").unwrap(),
+        "CPU usage",
+    );
+    let json = serde_json::to_string(&block).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"image","image_url":"https://example.com/graph.png","alt_text":"CPU usage"}"#
+    );
+}
@@ -0,0 +1,1003 @@
+use url::Url;
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use super::api;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use super::file::{CompleteUpload, Completed, File, UploadUrl, Uploaded};
+use super::message::Message;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use super::schedule::{DeleteScheduled, ScheduleMessage, Scheduled, ScheduledList};
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use super::user::{
+    LookupByEmail, OpenConversation, OpenDirectMessage, PostEphemeral, PostedEphemeral, User,
+};
+
+/// Public Slack Web API
+pub const DEFAULT_API_URL: &str = "https://slack.com/api";
+
+/// Slack App posting via the Web API with a bot token
+///
+/// Other than the [`Slack`](super::Slack) Incoming Webhook it can post to any channel the bot is a member of
+/// and returns the timestamp of the posted message.
+///
+/// Documentation: <https://api.slack.com/methods/chat.postMessage>
+///
+/// TLDR:
+/// - Create app
+/// - Add the `chat:write` scope to the bot token scopes
+/// - Install the app to the workspace and use the Bot User OAuth Token (`xoxb-…`)
+/// - Invite the bot to the channel
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bot {
+    /// Bot User OAuth Token (`xoxb-…`)
+    pub token: String,
+
+    /// Channel ID like `C1234567890` or a channel name like `#alerts`
+    ///
+    /// Uploading files and listing scheduled messages only work with the channel ID.
+    pub channel: String,
+
+    /// Web API to use. Defaults to [`DEFAULT_API_URL`]. Useful for a mock in tests.
+    pub api_url: Url,
+}
+
+/// Posted Slack message
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(from = "PostedResponse")]
+pub struct Posted {
+    /// ID of the channel the message was posted in
+    pub channel: String,
+
+    /// Timestamp of the message which is used as its ID
+    pub ts: String,
+
+    /// Timestamp of the parent message when this message is a reply in a thread
+    pub thread_ts: Option<String>,
+}
+
+impl Posted {
+    /// Thread to reply to this message in.
+    ///
+    /// When this message is a reply itself, the replies go into the same thread.
+    #[must_use]
+    pub fn thread(&self) -> Thread {
+        Thread {
+            channel: self.channel.clone(),
+            thread_ts: self.thread_ts.clone().unwrap_or_else(|| self.ts.clone()),
+            reply_broadcast: false,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct PostedResponse {
+    channel: String,
+    ts: String,
+    message: Option<PostedMessage>,
+}
+
+#[derive(serde::Deserialize)]
+struct PostedMessage {
+    thread_ts: Option<String>,
+}
+
+impl From<PostedResponse> for Posted {
+    fn from(response: PostedResponse) -> Self {
+        Self {
+            channel: response.channel,
+            ts: response.ts,
+            thread_ts: response.message.and_then(|message| message.thread_ts),
+        }
+    }
+}
+
+/// Thread to post replies into. Create it with [`Posted::thread`].
+///
+/// Documentation: <https://api.slack.com/messaging/managing#threading>
+#[allow(clippy::struct_field_names)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thread {
+    pub channel: String,
+
+    /// Timestamp of the parent message
+    pub thread_ts: String,
+
+    /// Also show the reply in the channel
+    pub reply_broadcast: bool,
+}
+
+#[derive(serde::Serialize)]
+struct PostMessage<'a> {
+    channel: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    reply_broadcast: bool,
+    #[serde(flatten)]
+    message: &'a Message,
+}
+
+#[derive(serde::Serialize)]
+struct UpdateMessage<'a> {
+    channel: &'a str,
+    ts: &'a str,
+    #[serde(flatten)]
+    message: &'a Message,
+}
+
+impl Bot {
+    #[must_use]
+    pub fn new(token: String, channel: String) -> Self {
+        Self {
+            token,
+            channel,
+            api_url: default_api_url(),
+        }
+    }
+
+    #[must_use]
+    fn post_message<'a>(&'a self, message: &'a Message) -> PostMessage<'a> {
+        PostMessage {
+            channel: &self.channel,
+            thread_ts: None,
+            reply_broadcast: false,
+            message,
+        }
+    }
+
+    #[must_use]
+    fn reply_message<'a>(thread: &'a Thread, message: &'a Message) -> PostMessage<'a> {
+        PostMessage {
+            channel: &thread.channel,
+            thread_ts: Some(&thread.thread_ts),
+            reply_broadcast: thread.reply_broadcast,
+            message,
+        }
+    }
+
+    #[must_use]
+    fn update_message<'a>(posted: &'a Posted, message: &'a Message) -> UpdateMessage<'a> {
+        UpdateMessage {
+            channel: &posted.channel,
+            ts: &posted.ts,
+            message,
+        }
+    }
+
+    /// Post a Slack message via [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn send_ureq(&self, text: &str) -> anyhow::Result<Posted> {
+        self.send_message_ureq(&Message::new(text))
+    }
+
+    /// Post a Slack message via [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn send_reqwest(&self, text: &str) -> anyhow::Result<Posted> {
+        self.send_message_reqwest(&Message::new(text)).await
+    }
+
+    /// Post a Slack message with [`Block`](super::Block)s via [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn send_message_ureq(&self, message: &Message) -> anyhow::Result<Posted> {
+        api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &self.post_message(message),
+        )
+    }
+
+    /// Post a Slack message with [`Block`](super::Block)s via [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn send_message_reqwest(&self, message: &Message) -> anyhow::Result<Posted> {
+        let client = api::reqwest_client()?;
+        api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &self.post_message(message),
+        )
+        .await
+    }
+
+    /// Reply in a thread via [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn reply_ureq(&self, thread: &Thread, message: &Message) -> anyhow::Result<Posted> {
+        api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &Self::reply_message(thread, message),
+        )
+    }
+
+    /// Reply in a thread via [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn reply_reqwest(
+        &self,
+        thread: &Thread,
+        message: &Message,
+    ) -> anyhow::Result<Posted> {
+        let client = api::reqwest_client()?;
+        api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &Self::reply_message(thread, message),
+        )
+        .await
+    }
+
+    /// Replace the content of a posted message via [`ureq`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.update>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn update_ureq(&self, posted: &Posted, message: &Message) -> anyhow::Result<Posted> {
+        api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.update",
+            &Self::update_message(posted, message),
+        )
+    }
+
+    /// Replace the content of a posted message via [`reqwest`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.update>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn update_reqwest(
+        &self,
+        posted: &Posted,
+        message: &Message,
+    ) -> anyhow::Result<Posted> {
+        let client = api::reqwest_client()?;
+        api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.update",
+            &Self::update_message(posted, message),
+        )
+        .await
+    }
+
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
+    #[must_use]
+    fn upload_url_form(file: &File) -> [(&'static str, String); 2] {
+        [
+            ("filename", file.filename.clone()),
+            ("length", file.content.len().to_string()),
+        ]
+    }
+
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
+    #[must_use]
+    fn complete_upload<'a>(
+        &'a self,
+        file: &'a File,
+        file_id: &'a str,
+        thread: Option<&'a Thread>,
+    ) -> CompleteUpload<'a> {
+        let channel = thread.map_or(self.channel.as_str(), |thread| &thread.channel);
+        let thread_ts = thread.map(|thread| thread.thread_ts.as_str());
+        CompleteUpload::new(file, file_id, channel, thread_ts)
+    }
+
+    /// Upload a file and share it in the channel or the thread via [`ureq`].
+    ///
+    /// The [`channel`](Self::channel) has to be a channel ID like `C1234567890` as names are not supported for files.
+    /// [`Thread::reply_broadcast`] is ignored.
+    /// Requires the `files:write` scope.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when one of the three upload steps fails.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn upload_ureq(&self, file: &File, thread: Option<&Thread>) -> anyhow::Result<Uploaded> {
+        use anyhow::Context as _;
+        let upload: UploadUrl = api::call_form_ureq(
+            &self.api_url,
+            &self.token,
+            "files.getUploadURLExternal",
+            &Self::upload_url_form(file),
+        )?;
+        api::upload_ureq(&upload.upload_url, &file.content)
+            .context("Failed to upload the file content to Slack")?;
+        let completed: Completed = api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "files.completeUploadExternal",
+            &self.complete_upload(file, &upload.file_id, thread),
+        )?;
+        completed
+            .files
+            .into_iter()
+            .next()
+            .context("Slack did not return the uploaded file")
+    }
+
+    /// Upload a file and share it in the channel or the thread via [`reqwest`].
+    ///
+    /// The [`channel`](Self::channel) has to be a channel ID like `C1234567890` as names are not supported for files.
+    /// [`Thread::reply_broadcast`] is ignored.
+    /// Requires the `files:write` scope.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when one of the three upload steps fails.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn upload_reqwest(
+        &self,
+        file: &File,
+        thread: Option<&Thread>,
+    ) -> anyhow::Result<Uploaded> {
+        use anyhow::Context as _;
+        let client = api::reqwest_client()?;
+        let upload: UploadUrl = api::call_form_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "files.getUploadURLExternal",
+            &Self::upload_url_form(file),
+        )
+        .await?;
+        api::upload_reqwest(&client, &upload.upload_url, &file.content)
+            .await
+            .context("Failed to upload the file content to Slack")?;
+        let completed: Completed = api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "files.completeUploadExternal",
+            &self.complete_upload(file, &upload.file_id, thread),
+        )
+        .await?;
+        completed
+            .files
+            .into_iter()
+            .next()
+            .context("Slack did not return the uploaded file")
+    }
+
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
+    #[must_use]
+    fn list_scheduled_form(&self, cursor: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut form = vec![("channel", self.channel.clone())];
+        if let Some(cursor) = cursor {
+            form.push(("cursor", cursor.to_owned()));
+        }
+        form
+    }
+
+    /// Schedule a message to be posted at the given unix timestamp via [`ureq`].
+    ///
+    /// The time has to be within the next 120 days.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.scheduleMessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn schedule_ureq(&self, post_at: i64, message: &Message) -> anyhow::Result<Scheduled> {
+        api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.scheduleMessage",
+            &ScheduleMessage {
+                channel: &self.channel,
+                post_at,
+                message,
+            },
+        )
+    }
+
+    /// Schedule a message to be posted at the given unix timestamp via [`reqwest`].
+    ///
+    /// The time has to be within the next 120 days.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.scheduleMessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn schedule_reqwest(
+        &self,
+        post_at: i64,
+        message: &Message,
+    ) -> anyhow::Result<Scheduled> {
+        let client = api::reqwest_client()?;
+        api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.scheduleMessage",
+            &ScheduleMessage {
+                channel: &self.channel,
+                post_at,
+                message,
+            },
+        )
+        .await
+    }
+
+    /// List the messages scheduled in the channel which were not posted yet via [`ureq`].
+    ///
+    /// The [`channel`](Self::channel) has to be a channel ID like `C1234567890` as names are not supported for listing.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.scheduledMessages.list>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn list_scheduled_ureq(&self) -> anyhow::Result<Vec<Scheduled>> {
+        let mut result = Vec::new();
+        let mut cursor = None;
+        loop {
+            let list: ScheduledList = api::call_form_ureq(
+                &self.api_url,
+                &self.token,
+                "chat.scheduledMessages.list",
+                &self.list_scheduled_form(cursor.as_deref()),
+            )?;
+            let next_cursor = list.next_cursor().map(ToOwned::to_owned);
+            anyhow::ensure!(
+                next_cursor.is_none() || next_cursor != cursor,
+                "Slack returned the same cursor for the scheduled messages again"
+            );
+            cursor = next_cursor;
+            result.extend(list.scheduled_messages);
+            if cursor.is_none() {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// List the messages scheduled in the channel which were not posted yet via [`reqwest`].
+    ///
+    /// The [`channel`](Self::channel) has to be a channel ID like `C1234567890` as names are not supported for listing.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.scheduledMessages.list>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn list_scheduled_reqwest(&self) -> anyhow::Result<Vec<Scheduled>> {
+        let client = api::reqwest_client()?;
+        let mut result = Vec::new();
+        let mut cursor = None;
+        loop {
+            let list: ScheduledList = api::call_form_reqwest(
+                &client,
+                &self.api_url,
+                &self.token,
+                "chat.scheduledMessages.list",
+                &self.list_scheduled_form(cursor.as_deref()),
+            )
+            .await?;
+            let next_cursor = list.next_cursor().map(ToOwned::to_owned);
+            anyhow::ensure!(
+                next_cursor.is_none() || next_cursor != cursor,
+                "Slack returned the same cursor for the scheduled messages again"
+            );
+            cursor = next_cursor;
+            result.extend(list.scheduled_messages);
+            if cursor.is_none() {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Cancel a scheduled message before it is posted via [`ureq`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.deleteScheduledMessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn cancel_scheduled_ureq(&self, scheduled: &Scheduled) -> anyhow::Result<()> {
+        let _: serde_json::Value = api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.deleteScheduledMessage",
+            &DeleteScheduled {
+                channel: &scheduled.channel,
+                scheduled_message_id: &scheduled.id,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Cancel a scheduled message before it is posted via [`reqwest`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.deleteScheduledMessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn cancel_scheduled_reqwest(&self, scheduled: &Scheduled) -> anyhow::Result<()> {
+        let client = api::reqwest_client()?;
+        let _: serde_json::Value = api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.deleteScheduledMessage",
+            &DeleteScheduled {
+                channel: &scheduled.channel,
+                scheduled_message_id: &scheduled.id,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "ureq")]
+    fn user_id_ureq(&self, user: &User) -> anyhow::Result<String> {
+        match user {
+            User::Id(id) => Ok(id.clone()),
+            User::Email(email) => {
+                let lookup: LookupByEmail = api::call_form_ureq(
+                    &self.api_url,
+                    &self.token,
+                    "users.lookupByEmail",
+                    &[("email", email.clone())],
+                )?;
+                Ok(lookup.user.id)
+            }
+        }
+    }
+
+    #[cfg(feature = "reqwest")]
+    async fn user_id_reqwest(
+        &self,
+        client: &reqwest::Client,
+        user: &User,
+    ) -> anyhow::Result<String> {
+        match user {
+            User::Id(id) => Ok(id.clone()),
+            User::Email(email) => {
+                let lookup: LookupByEmail = api::call_form_reqwest(
+                    client,
+                    &self.api_url,
+                    &self.token,
+                    "users.lookupByEmail",
+                    &[("email", email.clone())],
+                )
+                .await?;
+                Ok(lookup.user.id)
+            }
+        }
+    }
+
+    /// Send a direct message to a user via [`ureq`].
+    ///
+    /// Requires the `im:write` scope and `users:read.email` when the user is given by [`User::Email`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/conversations.open>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn direct_message_ureq(&self, user: &User, message: &Message) -> anyhow::Result<Posted> {
+        let user_id = self.user_id_ureq(user)?;
+        let open: OpenConversation = api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "conversations.open",
+            &OpenDirectMessage { users: &user_id },
+        )?;
+        api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &PostMessage {
+                channel: &open.channel.id,
+                thread_ts: None,
+                reply_broadcast: false,
+                message,
+            },
+        )
+    }
+
+    /// Send a direct message to a user via [`reqwest`].
+    ///
+    /// Requires the `im:write` scope and `users:read.email` when the user is given by [`User::Email`].
+    ///
+    /// Documentation: <https://api.slack.com/methods/conversations.open>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn direct_message_reqwest(
+        &self,
+        user: &User,
+        message: &Message,
+    ) -> anyhow::Result<Posted> {
+        let client = api::reqwest_client()?;
+        let user_id = self.user_id_reqwest(&client, user).await?;
+        let open: OpenConversation = api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "conversations.open",
+            &OpenDirectMessage { users: &user_id },
+        )
+        .await?;
+        api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.postMessage",
+            &PostMessage {
+                channel: &open.channel.id,
+                thread_ts: None,
+                reply_broadcast: false,
+                message,
+            },
+        )
+        .await
+    }
+
+    /// Show a message in the channel only to the given user via [`ureq`].
+    ///
+    /// The user has to be a member of the channel.
+    /// Ephemeral messages are not persisted, so only the timestamp of the message is returned.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.postEphemeral>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "ureq")]
+    pub fn ephemeral_ureq(&self, user: &User, message: &Message) -> anyhow::Result<String> {
+        let user_id = self.user_id_ureq(user)?;
+        let posted: PostedEphemeral = api::call_ureq(
+            &self.api_url,
+            &self.token,
+            "chat.postEphemeral",
+            &PostEphemeral {
+                channel: &self.channel,
+                user: &user_id,
+                message,
+            },
+        )?;
+        Ok(posted.message_ts)
+    }
+
+    /// Show a message in the channel only to the given user via [`reqwest`].
+    ///
+    /// The user has to be a member of the channel.
+    /// Ephemeral messages are not persisted, so only the timestamp of the message is returned.
+    ///
+    /// Documentation: <https://api.slack.com/methods/chat.postEphemeral>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`ApiError`](super::ApiError).
+    #[cfg(feature = "reqwest")]
+    pub async fn ephemeral_reqwest(
+        &self,
+        user: &User,
+        message: &Message,
+    ) -> anyhow::Result<String> {
+        let client = api::reqwest_client()?;
+        let user_id = self.user_id_reqwest(&client, user).await?;
+        let posted: PostedEphemeral = api::call_reqwest(
+            &client,
+            &self.api_url,
+            &self.token,
+            "chat.postEphemeral",
+            &PostEphemeral {
+                channel: &self.channel,
+                user: &user_id,
+                message,
+            },
+        )
+        .await?;
+        Ok(posted.message_ts)
+    }
+}
+
+fn default_api_url() -> Url {
+    Url::parse(DEFAULT_API_URL).expect("default API URL should be valid")
+}
+
+#[test]
+fn post_message_json() {
+    let bot = Bot::new("xoxb-123".to_owned(), "C123".to_owned());
+    let message = Message::new("hello");
+    let json = serde_json::to_string(&bot.post_message(&message)).unwrap();
+    assert_eq!(json, r#"{"channel":"C123","text":"hello"}"#);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn post_message_to_fake_api() {
+    let api = crate::fake_api::FakeApi::start(|_| {
+        r#"{"ok":true,"channel":"C123","ts":"1503435956.000247","message":{"text":"hello"}}"#
+            .to_owned()
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "#alerts".to_owned())
+    };
+    let posted = bot.send_ureq("hello").unwrap();
+    assert_eq!(posted.channel, "C123");
+    assert_eq!(posted.ts, "1503435956.000247");
+
+    let requests = api.requests();
+    assert_eq!(requests[0].method, "chat.postMessage");
+    assert_eq!(
+        requests[0].authorization.as_deref(),
+        Some("Bearer xoxb-123")
+    );
+    assert_eq!(requests[0].json()["channel"], "#alerts");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn post_message_error() {
+    let api =
+        crate::fake_api::FakeApi::start(|_| r#"{"ok":false,"error":"not_in_channel"}"#.to_owned());
+    let bot = Bot {
+        api_url: api.url,
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let error = bot.send_ureq("hello").unwrap_err();
+    assert_eq!(error.to_string(), "Slack API error: not_in_channel");
+}
+
+#[test]
+fn reply_message_json() {
+    let thread = Thread {
+        reply_broadcast: true,
+        ..Posted {
+            channel: "C123".to_owned(),
+            ts: "2.000".to_owned(),
+            thread_ts: Some("1.000".to_owned()),
+        }
+        .thread()
+    };
+    let message = Message::new("still failing");
+    let json = serde_json::to_string(&Bot::reply_message(&thread, &message)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"channel":"C123","thread_ts":"1.000","reply_broadcast":true,"text":"still failing"}"#
+    );
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn reply_and_update() {
+    let api = crate::fake_api::FakeApi::start(|request| {
+        let json = request.json();
+        if json.get("thread_ts").is_some() {
+            r#"{"ok":true,"channel":"C123","ts":"2.000","message":{"text":"reply","thread_ts":"1.000"}}"#.to_owned()
+        } else {
+            r#"{"ok":true,"channel":"C123","ts":"1.000","message":{"text":"status"}}"#.to_owned()
+        }
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "#alerts".to_owned())
+    };
+    let posted = bot.send_ureq("Deploy running").unwrap();
+    assert_eq!(posted.thread_ts, None);
+    let reply = bot
+        .reply_ureq(&posted.thread(), &Message::new("Step 1 done"))
+        .unwrap();
+    assert_eq!(reply.thread_ts.as_deref(), Some("1.000"));
+    bot.update_ureq(&posted, &Message::new("Deploy done"))
+        .unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests[1].json()["channel"], "C123");
+    assert_eq!(requests[1].json()["thread_ts"], "1.000");
+    assert_eq!(requests[2].method, "chat.update");
+    assert_eq!(requests[2].json()["ts"], "1.000");
+    assert_eq!(requests[2].json()["text"], "Deploy done");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn upload_file() {
+    let upload_url = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+    let api = crate::fake_api::FakeApi::start({
+        let upload_url = std::sync::Arc::clone(&upload_url);
+        move |request| {
+            match request.method.as_str() {
+        "files.getUploadURLExternal" => format!(
+            r#"{{"ok":true,"upload_url":"{}","file_id":"F123"}}"#,
+            upload_url.lock().unwrap()
+        ),
+        "F123" => "OK - 13".to_owned(),
+        _ => r#"{"ok":true,"files":[{"id":"F123","title":"Test report","permalink":"https://example.slack.com/files/U1/F123/report.html"}]}"#.to_owned(),
+    }
+        }
+    });
+    *upload_url.lock().unwrap() = format!("{}upload/F123", api.url);
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let file = File::new("report.html", b"<html></html>".to_vec()).title("Test report");
+    let thread = Thread {
+        channel: "C456".to_owned(),
+        thread_ts: "1.000".to_owned(),
+        reply_broadcast: false,
+    };
+    let uploaded = bot.upload_ureq(&file, Some(&thread)).unwrap();
+    assert_eq!(uploaded.id, "F123");
+    assert_eq!(uploaded.title.as_deref(), Some("Test report"));
+
+    let requests = api.requests();
+    assert_eq!(requests[0].get("filename"), Some("report.html"));
+    assert_eq!(requests[0].get("length"), Some("13"));
+    assert_eq!(requests[1].body, "<html></html>");
+    assert_eq!(requests[1].authorization, None);
+    assert_eq!(requests[2].method, "files.completeUploadExternal");
+    assert_eq!(requests[2].json()["channel_id"], "C456");
+    assert_eq!(requests[2].json()["thread_ts"], "1.000");
+    assert_eq!(requests[2].json()["files"][0]["id"], "F123");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn schedule_list_and_cancel() {
+    let api = crate::fake_api::FakeApi::start(|request| {
+        match request.method.as_str() {
+        "chat.scheduleMessage" => r#"{"ok":true,"channel":"C123","scheduled_message_id":"Q1","post_at":1900000000,"message":{"text":"Maintenance"}}"#.to_owned(),
+        "chat.scheduledMessages.list" if request.get("cursor").is_none() => r#"{"ok":true,"scheduled_messages":[{"id":"Q1","channel_id":"C123","post_at":1900000000,"date_created":1800000000,"text":"Maintenance"}],"response_metadata":{"next_cursor":"page2"}}"#.to_owned(),
+        "chat.scheduledMessages.list" => r#"{"ok":true,"scheduled_messages":[{"id":"Q2","channel_id":"C123","post_at":1900000600,"date_created":1800000000,"text":"Maintenance over"}],"response_metadata":{"next_cursor":""}}"#.to_owned(),
+        _ => r#"{"ok":true}"#.to_owned(),
+    }
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let scheduled = bot
+        .schedule_ureq(1_900_000_000, &Message::new("Maintenance"))
+        .unwrap();
+    assert_eq!(scheduled.id, "Q1");
+    let list = bot.list_scheduled_ureq().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0], scheduled);
+    bot.cancel_scheduled_ureq(&scheduled).unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests[0].json()["post_at"], 1_900_000_000);
+    assert_eq!(requests[0].json()["text"], "Maintenance");
+    assert_eq!(requests[2].get("cursor"), Some("page2"));
+    assert_eq!(requests[3].method, "chat.deleteScheduledMessage");
+    assert_eq!(requests[3].json()["scheduled_message_id"], "Q1");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn list_scheduled_stops_on_repeated_cursor() {
+    let api = crate::fake_api::FakeApi::start(|_| {
+        r#"{"ok":true,"scheduled_messages":[],"response_metadata":{"next_cursor":"same"}}"#
+            .to_owned()
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let error = bot.list_scheduled_ureq().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Slack returned the same cursor for the scheduled messages again"
+    );
+    assert_eq!(api.requests().len(), 2);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn direct_message_by_email() {
+    let api = crate::fake_api::FakeApi::start(|request| match request.method.as_str() {
+        "users.lookupByEmail" => r#"{"ok":true,"user":{"id":"U123"}}"#.to_owned(),
+        "conversations.open" => r#"{"ok":true,"channel":{"id":"D123"}}"#.to_owned(),
+        _ => r#"{"ok":true,"channel":"D123","ts":"1.000","message":{"text":"Your job failed"}}"#
+            .to_owned(),
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let posted = bot
+        .direct_message_ureq(
+            &User::Email("jane@example.com".to_owned()),
+            &Message::new("Your job failed"),
+        )
+        .unwrap();
+    assert_eq!(posted.channel, "D123");
+
+    let requests = api.requests();
+    assert_eq!(requests[0].get("email"), Some("jane@example.com"));
+    assert_eq!(requests[1].json()["users"], "U123");
+    assert_eq!(requests[2].json()["channel"], "D123");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn ephemeral_by_id() {
+    let api = crate::fake_api::FakeApi::start(|_| {
+        r#"{"ok":true,"message_ts":"1502210682.580145"}"#.to_owned()
+    });
+    let bot = Bot {
+        api_url: api.url.clone(),
+        ..Bot::new("xoxb-123".to_owned(), "C123".to_owned())
+    };
+    let ts = bot
+        .ephemeral_ureq(&User::Id("U123".to_owned()), &Message::new("Only you"))
+        .unwrap();
+    assert_eq!(ts, "1502210682.580145");
+
+    let requests = api.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "chat.postEphemeral");
+    assert_eq!(requests[0].json()["channel"], "C123");
+    assert_eq!(requests[0].json()["user"], "U123");
+}
@@ -0,0 +1,121 @@
+use url::Url;
+
+/// File to upload with [`Bot::upload_ureq`](super::Bot::upload_ureq)
+///
+/// Documentation: <https://api.slack.com/messaging/files#uploading_files>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// Name of the file like `report.html`
+    pub filename: String,
+
+    pub content: Vec<u8>,
+
+    /// Title shown instead of the filename
+    pub title: Option<String>,
+
+    /// Message text introducing the file. Interpreted as `mrkdwn`.
+    pub initial_comment: Option<String>,
+}
+
+impl File {
+    #[must_use]
+    pub fn new(filename: &str, content: Vec<u8>) -> Self {
+        Self {
+            filename: filename.to_owned(),
+            content,
+            title: None,
+            initial_comment: None,
+        }
+    }
+
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+
+    #[must_use]
+    pub fn initial_comment(mut self, mrkdwn: &str) -> Self {
+        self.initial_comment = Some(mrkdwn.to_owned());
+        self
+    }
+}
+
+/// Uploaded and shared Slack file
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Uploaded {
+    /// File ID like `F0123456789`
+    pub id: String,
+
+    pub title: Option<String>,
+
+    /// Link to the file in the Slack workspace
+    pub permalink: Option<Url>,
+}
+
+/// Result of `files.getUploadURLExternal`
+///
+/// Documentation: <https://api.slack.com/methods/files.getUploadURLExternal>
+#[derive(Debug, serde::Deserialize)]
+pub struct UploadUrl {
+    pub upload_url: String,
+    pub file_id: String,
+}
+
+/// Result of `files.completeUploadExternal`
+///
+/// Documentation: <https://api.slack.com/methods/files.completeUploadExternal>
+#[derive(Debug, serde::Deserialize)]
+pub struct Completed {
+    pub files: Vec<Uploaded>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CompleteUpload<'a> {
+    pub files: [CompleteFile<'a>; 1],
+    pub channel_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_comment: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_ts: Option<&'a str>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CompleteFile<'a> {
+    pub id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<&'a str>,
+}
+
+impl<'a> CompleteUpload<'a> {
+    #[must_use]
+    pub fn new(
+        file: &'a File,
+        file_id: &'a str,
+        channel_id: &'a str,
+        thread_ts: Option<&'a str>,
+    ) -> Self {
+        Self {
+            files: [CompleteFile {
+                id: file_id,
+                title: file.title.as_deref(),
+            }],
+            channel_id,
+            initial_comment: file.initial_comment.as_deref(),
+            thread_ts,
+        }
+    }
+}
+
+#[test]
+fn complete_upload_json() {
+    let file = File::new("report.html", b"<html></html>".to_vec())
+        .title("Test report")
+        .initial_comment("3 tests failed");
+    let json =
+        serde_json::to_string(&CompleteUpload::new(&file, "F123", "C123", Some("1.000"))).unwrap();
+    assert_eq!(
+        json,
+        r#"{"files":[{"id":"F123","title":"Test report"}],"channel_id":"C123","initial_comment":"3 tests failed","thread_ts":"1.000"}"#
+    );
+}
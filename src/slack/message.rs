@@ -0,0 +1,77 @@
+use super::attachment::Attachment;
+use super::block::Block;
+
+/// Content of a Slack message
+///
+/// The `text` is shown in notifications and as a fallback when the blocks can not be shown.
+/// [`Attachment`]s are shown below the blocks.
+///
+/// ```
+/// use pling::{SlackBlock, SlackMessage};
+///
+/// let message = SlackMessage::new("Build failed for main")
+///     .block(SlackBlock::header("Build failed"))
+///     .block(SlackBlock::fields([
+///         ("Branch", "main"),
+///         ("Duration", "3m 12s"),
+///     ]));
+/// assert_eq!(message.blocks.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[must_use]
+pub struct Message {
+    pub text: String,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<Block>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+}
+
+impl Message {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            blocks: Vec::new(),
+            attachments: Vec::new(),
+        }
+    }
+
+    pub fn block(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    pub fn attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+#[test]
+fn json_with_blocks() {
+    let message = Message::new("fallback").block(Block::Divider);
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"text":"fallback","blocks":[{"type":"divider"}]}"#
+    );
+}
+
+#[test]
+fn json_with_attachment() {
+    use super::attachment::Severity;
+
+    let message =
+        Message::new("fallback").attachment(Attachment::new(Severity::Good).title("Healthy"));
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"text":"fallback","attachments":[{"color":"good","title":"Healthy"}]}"#
+    );
+}
@@ -0,0 +1,171 @@
+use url::Url;
+
+pub use self::api::ApiError;
+pub use self::attachment::{Attachment, Field, Severity};
+pub use self::block::{Block, Button, ButtonStyle, Text};
+pub use self::bot::{Bot, Posted, Thread, DEFAULT_API_URL};
+pub use self::file::{File, Uploaded};
+pub use self::message::Message;
+pub use self::mrkdwn::Mrkdwn;
+pub use self::options::{Icon, MessageOptions};
+pub use self::schedule::Scheduled;
+pub use self::user::User;
+pub use self::webhook_error::WebhookError;
+
+mod api;
+mod attachment;
+mod block;
+mod bot;
+mod file;
+mod message;
+mod mrkdwn;
+mod options;
+mod schedule;
+mod user;
+mod webhook_error;
+
+/// Documentation: <https://api.slack.com/messaging/webhooks#getting_started>
+///
+/// TLDR:
+/// - Create app
+/// - Enable Incoming Webhooks
+/// - Use the <https://hooks.slack.com/…> URL
+///
+/// Construct it with [`Slack::new`] and change the sender with the builder methods of [`MessageOptions`].
+pub struct Slack {
+    pub webhook: Url,
+
+    /// Channel, sender name, icon and link previews of the sent messages
+    pub options: MessageOptions,
+}
+
+#[derive(serde::Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    options: &'a MessageOptions,
+    #[serde(flatten)]
+    message: &'a Message,
+}
+
+impl Slack {
+    #[must_use]
+    pub fn new(webhook: Url) -> Self {
+        Self {
+            webhook,
+            options: MessageOptions::default(),
+        }
+    }
+
+    #[must_use]
+    fn payload_to_json(&self, message: &Message) -> String {
+        serde_json::to_string(&Payload {
+            options: &self.options,
+            message,
+        })
+        .expect("Slack payload should always be serializable")
+    }
+
+    /// Send a Slack notification via [`ureq`].
+    ///
+    /// The text is interpreted as `mrkdwn`. Use [`Mrkdwn`] to escape generated text.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`WebhookError`].
+    #[cfg(feature = "ureq")]
+    pub fn send_ureq(&self, text: &str) -> anyhow::Result<()> {
+        self.send_message_ureq(&Message::new(text))
+    }
+
+    /// Send a Slack message with [`Block`]s via [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`WebhookError`].
+    #[cfg(feature = "ureq")]
+    pub fn send_message_ureq(&self, message: &Message) -> anyhow::Result<()> {
+        let mut response = ureq::post(self.webhook.as_str())
+            .header(ureq::http::header::USER_AGENT, crate::USER_AGENT_UREQ)
+            .header(ureq::http::header::CONTENT_TYPE, "application/json")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .send(self.payload_to_json(message))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.body_mut().read_to_string()?;
+        Err(WebhookError::from_response(status.as_u16(), &body).into())
+    }
+
+    /// Send a Slack notification via [`reqwest`].
+    ///
+    /// The text is interpreted as `mrkdwn`. Use [`Mrkdwn`] to escape generated text.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`WebhookError`].
+    #[cfg(feature = "reqwest")]
+    pub async fn send_reqwest(&self, text: &str) -> anyhow::Result<()> {
+        self.send_message_reqwest(&Message::new(text)).await
+    }
+
+    /// Send a Slack message with [`Block`]s via [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Slack API.
+    /// Errors returned by Slack can be downcasted to [`WebhookError`].
+    #[cfg(feature = "reqwest")]
+    pub async fn send_message_reqwest(&self, message: &Message) -> anyhow::Result<()> {
+        let response = reqwest::ClientBuilder::new()
+            .user_agent(crate::USER_AGENT_REQWEST)
+            .build()?
+            .post(self.webhook.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(self.payload_to_json(message))
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.map_err(reqwest::Error::without_url)?;
+        Err(WebhookError::from_response(status.as_u16(), &body).into())
+    }
+}
+
+#[test]
+fn generating_payload_works() {
+    let slack = Slack::new(Url::parse("https://hooks.slack.com/services/T0/B0/X").unwrap());
+    let result = slack.payload_to_json(&Message::new("hello world"));
+    assert_eq!(result, r#"{"text":"hello world"}"#);
+}
+
+#[test]
+fn generating_payload_with_quotes_works() {
+    let slack = Slack::new(Url::parse("https://hooks.slack.com/services/T0/B0/X").unwrap());
+    let result = slack.payload_to_json(&Message::new(r#"hello "world""#));
+    assert_eq!(result, r#"{"text":"hello \"world\""}"#);
+}
+
+#[test]
+fn payload_with_options() {
+    let slack = Slack {
+        options: MessageOptions::default()
+            .username("Nightly Tests")
+            .icon_emoji(":test_tube:")
+            .unfurl_links(false),
+        ..Slack::new(Url::parse("https://hooks.slack.com/services/T0/B0/X").unwrap())
+    };
+    let result = slack.payload_to_json(&Message::new("hello"));
+    assert_eq!(
+        result,
+        r#"{"username":"Nightly Tests","icon_emoji":":test_tube:","unfurl_links":false,"text":"hello"}"#
+    );
+}
@@ -0,0 +1,209 @@
+use url::Url;
+
+/// Build text in Slack's `mrkdwn` syntax where all given texts are shown as they are.
+///
+/// `mrkdwn` can not escape the characters which end formatting, so inside of it they are replaced by look-alikes:
+/// `*` by `∗` in bold, `_` by `ˍ` in italic, `~` by `∼` in strikethrough, `` ` `` by `ˋ` in code
+/// and `|` by `¦` in dates.
+///
+/// Documentation: <https://api.slack.com/reference/surfaces/formatting>
+///
+/// ```
+/// use pling::SlackMrkdwn;
+///
+/// let url = url::Url::parse("https://example.com/logs").unwrap();
+/// let text = SlackMrkdwn::new()
+///     .here()
+///     .text(" Build failed: ")
+///     .code("a < b && c > d")
+///     .text(" ")
+///     .link(&url, "Logs")
+///     .build();
+/// assert_eq!(
+///     text,
+///     "<!here> Build failed: `a &lt; b &amp;&amp; c &gt; d` <https://example.com/logs|Logs>"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Mrkdwn {
+    text: String,
+}
+
+impl Mrkdwn {
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+        }
+    }
+
+    /// Escape `&`, `<` and `>` so the text is not interpreted as special syntax.
+    ///
+    /// ```
+    /// use pling::SlackMrkdwn;
+    ///
+    /// assert_eq!(SlackMrkdwn::escape("<b> & co"), "&lt;b&gt; &amp; co");
+    /// ```
+    #[must_use]
+    pub fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    /// Plain text which is escaped
+    pub fn text(mut self, text: &str) -> Self {
+        self.text += &Self::escape(text);
+        self
+    }
+
+    /// Text which is already in `mrkdwn` syntax and is not escaped
+    pub fn raw(mut self, mrkdwn: &str) -> Self {
+        self.text += mrkdwn;
+        self
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.wrapped('*', "∗", text)
+    }
+
+    pub fn italic(self, text: &str) -> Self {
+        self.wrapped('_', "ˍ", text)
+    }
+
+    pub fn strike(self, text: &str) -> Self {
+        self.wrapped('~', "∼", text)
+    }
+
+    /// Inline monospace text
+    pub fn code(self, code: &str) -> Self {
+        self.wrapped('`', "ˋ", code)
+    }
+
+    fn wrapped(mut self, wrap: char, look_alike: &str, text: &str) -> Self {
+        self.text.push(wrap);
+        self.text += &Self::escape(text).replace(wrap, look_alike);
+        self.text.push(wrap);
+        self
+    }
+
+    /// Link with a label: `<url|label>`
+    pub fn link(self, url: &Url, label: &str) -> Self {
+        let url = url.as_str().replace('|', "%7C");
+        self.raw(&format!("<{}|{}>", Self::escape(&url), Self::escape(label)))
+    }
+
+    /// Mention a user by its ID like `U0123456789`: `<@U0123456789>`
+    pub fn user(self, user_id: &str) -> Self {
+        self.raw(&format!("<@{user_id}>"))
+    }
+
+    /// Link to a channel by its ID like `C0123456789`: `<#C0123456789>`
+    pub fn channel(self, channel_id: &str) -> Self {
+        self.raw(&format!("<#{channel_id}>"))
+    }
+
+    /// Mention a user group by its ID like `S0123456789`: `<!subteam^S0123456789>`
+    pub fn user_group(self, group_id: &str) -> Self {
+        self.raw(&format!("<!subteam^{group_id}>"))
+    }
+
+    /// Notify the active members of the channel: `<!here>`
+    pub fn here(self) -> Self {
+        self.raw("<!here>")
+    }
+
+    /// Notify all members of the channel: `<!channel>`
+    pub fn everyone_in_channel(self) -> Self {
+        self.raw("<!channel>")
+    }
+
+    /// Date shown in the timezone of the reader: `<!date^timestamp^format|fallback>`
+    ///
+    /// The format can contain tokens like `{date_short}`, `{date_pretty}` or `{time}` and other text.
+    /// The fallback is shown when the client can not render the date.
+    ///
+    /// Documentation: <https://api.slack.com/reference/surfaces/formatting#date-formatting>
+    pub fn date(self, unix_timestamp: i64, format: &str, fallback: &str) -> Self {
+        self.raw(&format!(
+            "<!date^{unix_timestamp}^{}|{}>",
+            Self::escape(format).replace('|', "¦"),
+            Self::escape(fallback).replace('|', "¦")
+        ))
+    }
+
+    #[must_use]
+    pub fn build(self) -> String {
+        self.text
+    }
+}
+
+#[test]
+fn escape_all() {
+    assert_eq!(Mrkdwn::escape("&<>&amp;"), "&amp;&lt;&gt;&amp;amp;");
+}
+
+#[test]
+fn formatting_is_escaped() {
+    let text = Mrkdwn::new()
+        .bold("<@U123>")
+        .italic("a&b")
+        .strike("x")
+        .build();
+    assert_eq!(text, "*&lt;@U123&gt;*_a&amp;b_~x~");
+}
+
+#[test]
+fn formatting_replaces_wrap_char() {
+    let text = Mrkdwn::new()
+        .bold("a*b")
+        .italic("snake_case")
+        .strike("~x~")
+        .code("a`b")
+        .build();
+    assert_eq!(text, "*a∗b*_snakeˍcase_~∼x∼~`aˋb`");
+}
+
+#[test]
+fn link_escapes_label_and_pipe() {
+    let url = Url::parse("https://example.com/?a=1&b=x|y").unwrap();
+    let text = Mrkdwn::new().link(&url, "a > b").build();
+    assert_eq!(text, "<https://example.com/?a=1&amp;b=x%7Cy|a &gt; b>");
+}
+
+#[test]
+fn mentions() {
+    let text = Mrkdwn::new()
+        .user("U123")
+        .text(" ")
+        .user_group("S456")
+        .text(" ")
+        .channel("C789")
+        .text(" ")
+        .everyone_in_channel()
+        .build();
+    assert_eq!(text, "<@U123> <!subteam^S456> <#C789> <!channel>");
+}
+
+#[test]
+fn date() {
+    let text = Mrkdwn::new()
+        .date(
+            1_392_734_382,
+            "{date_short} at {time}",
+            "Feb 18, 2014 at 6:39 AM UTC",
+        )
+        .build();
+    assert_eq!(
+        text,
+        "<!date^1392734382^{date_short} at {time}|Feb 18, 2014 at 6:39 AM UTC>"
+    );
+}
+
+#[test]
+fn date_replaces_pipe() {
+    let text = Mrkdwn::new()
+        .date(0, "{date} | {time}", "1970-01-01 | 00:00")
+        .build();
+    assert_eq!(text, "<!date^0^{date} ¦ {time}|1970-01-01 ¦ 00:00>");
+}
@@ -0,0 +1,114 @@
+use url::Url;
+
+/// Change how a message sent via an Incoming Webhook appears
+///
+/// ```
+/// use pling::SlackMessageOptions;
+///
+/// let options = SlackMessageOptions::default()
+///     .username("Nightly Tests")
+///     .icon_emoji(":test_tube:")
+///     .unfurl_links(false);
+/// assert_eq!(options.username.as_deref(), Some("Nightly Tests"));
+/// ```
+///
+/// Documentation: <https://api.slack.com/methods/chat.postMessage#arg_username>
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[must_use]
+pub struct MessageOptions {
+    /// Channel like `#alerts` or `@user` to post to instead of the default channel of the webhook.
+    ///
+    /// Only legacy webhooks of custom integrations can change the channel.
+    /// Webhooks of Slack apps are bound to one channel and ignore this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Name shown as the sender instead of the app name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Image shown as the sender instead of the app icon
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Icon>,
+
+    /// Show previews of text-based links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_links: Option<bool>,
+
+    /// Show previews of media links like images or videos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_media: Option<bool>,
+}
+
+impl MessageOptions {
+    pub fn channel(mut self, channel: &str) -> Self {
+        self.channel = Some(channel.to_owned());
+        self
+    }
+
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_owned());
+        self
+    }
+
+    pub fn icon_emoji(mut self, emoji: &str) -> Self {
+        self.icon = Some(Icon::Emoji(emoji.to_owned()));
+        self
+    }
+
+    pub fn icon_url(mut self, url: Url) -> Self {
+        self.icon = Some(Icon::Url(url));
+        self
+    }
+
+    pub const fn unfurl_links(mut self, unfurl: bool) -> Self {
+        self.unfurl_links = Some(unfurl);
+        self
+    }
+
+    pub const fn unfurl_media(mut self, unfurl: bool) -> Self {
+        self.unfurl_media = Some(unfurl);
+        self
+    }
+}
+
+/// Image shown as the sender of a message
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum Icon {
+    /// Emoji like `:robot_face:`
+    #[serde(rename = "icon_emoji")]
+    Emoji(String),
+
+    /// URL to an image
+    #[serde(rename = "icon_url")]
+    Url(Url),
+}
+
+#[test]
+fn default_is_empty() {
+    let json = serde_json::to_string(&MessageOptions::default()).unwrap();
+    assert_eq!(json, "{}");
+}
+
+#[test]
+fn all_options_json() {
+    let options = MessageOptions::default()
+        .channel("#alerts")
+        .username("CI")
+        .icon_emoji(":robot_face:")
+        .unfurl_links(false)
+        .unfurl_media(true);
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(
+        json,
+        r##"{"channel":"#alerts","username":"CI","icon_emoji":":robot_face:","unfurl_links":false,"unfurl_media":true}"##
+    );
+}
+
+#[test]
+fn icon_url_json() {
+    let options =
+        MessageOptions::default().icon_url(Url::parse("https://example.com/icon.png").unwrap());
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(json, r#"{"icon_url":"https://example.com/icon.png"}"#);
+}
@@ -0,0 +1,78 @@
+use super::message::Message;
+
+/// Message which is posted by Slack at a later time
+///
+/// Documentation: <https://api.slack.com/messaging/scheduling>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Scheduled {
+    /// ID like `Q1298393284` to cancel the message with
+    #[serde(alias = "scheduled_message_id")]
+    pub id: String,
+
+    /// ID of the channel the message will be posted in
+    #[serde(alias = "channel_id")]
+    pub channel: String,
+
+    /// Unix timestamp of when the message will be posted
+    pub post_at: i64,
+}
+
+#[derive(serde::Serialize)]
+pub struct ScheduleMessage<'a> {
+    pub channel: &'a str,
+    pub post_at: i64,
+    #[serde(flatten)]
+    pub message: &'a Message,
+}
+
+/// Result of `chat.scheduledMessages.list`
+///
+/// Documentation: <https://api.slack.com/methods/chat.scheduledMessages.list>
+#[derive(serde::Deserialize)]
+pub struct ScheduledList {
+    pub scheduled_messages: Vec<Scheduled>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+#[derive(Default, serde::Deserialize)]
+pub struct ResponseMetadata {
+    #[serde(default)]
+    pub next_cursor: String,
+}
+
+impl ScheduledList {
+    /// Cursor of the next page when there are more scheduled messages
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        Some(self.response_metadata.next_cursor.as_str()).filter(|cursor| !cursor.is_empty())
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct DeleteScheduled<'a> {
+    pub channel: &'a str,
+    pub scheduled_message_id: &'a str,
+}
+
+#[test]
+fn schedule_response() {
+    let scheduled = serde_json::from_str::<Scheduled>(
+        r#"{"ok":true,"channel":"C123","scheduled_message_id":"Q123","post_at":1562180400,"message":{"text":"Maintenance starts in 10 minutes"}}"#,
+    )
+    .unwrap();
+    assert_eq!(scheduled.id, "Q123");
+    assert_eq!(scheduled.channel, "C123");
+    assert_eq!(scheduled.post_at, 1_562_180_400);
+}
+
+#[test]
+fn list_response() {
+    let list = serde_json::from_str::<ScheduledList>(
+        r#"{"ok":true,"scheduled_messages":[{"id":"Q123","channel_id":"C123","post_at":1562180400,"date_created":1562178400,"text":"Maintenance"}],"response_metadata":{"next_cursor":""}}"#,
+    )
+    .unwrap();
+    assert_eq!(list.scheduled_messages[0].id, "Q123");
+    assert_eq!(list.scheduled_messages[0].channel, "C123");
+    assert_eq!(list.next_cursor(), None);
+}
@@ -0,0 +1,79 @@
+use super::message::Message;
+
+/// Slack user to send a direct or ephemeral message to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum User {
+    /// User ID like `U0123456789`
+    Id(String),
+
+    /// Email address of the user which is resolved via `users.lookupByEmail`.
+    ///
+    /// Requires the `users:read.email` scope.
+    Email(String),
+}
+
+/// Result of `users.lookupByEmail`
+///
+/// Documentation: <https://api.slack.com/methods/users.lookupByEmail>
+#[derive(serde::Deserialize)]
+pub struct LookupByEmail {
+    pub user: WithId,
+}
+
+/// Result of `conversations.open`
+///
+/// Documentation: <https://api.slack.com/methods/conversations.open>
+#[derive(serde::Deserialize)]
+pub struct OpenConversation {
+    pub channel: WithId,
+}
+
+#[derive(serde::Deserialize)]
+pub struct WithId {
+    pub id: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct OpenDirectMessage<'a> {
+    pub users: &'a str,
+}
+
+#[derive(serde::Serialize)]
+pub struct PostEphemeral<'a> {
+    pub channel: &'a str,
+    pub user: &'a str,
+    #[serde(flatten)]
+    pub message: &'a Message,
+}
+
+/// Result of `chat.postEphemeral`
+///
+/// Documentation: <https://api.slack.com/methods/chat.postEphemeral>
+#[derive(serde::Deserialize)]
+pub struct PostedEphemeral {
+    pub message_ts: String,
+}
+
+#[test]
+fn lookup_by_email_response() {
+    let lookup = serde_json::from_str::<LookupByEmail>(
+        r#"{"ok":true,"user":{"id":"U123","team_id":"T123","name":"spengler"}}"#,
+    )
+    .unwrap();
+    assert_eq!(lookup.user.id, "U123");
+}
+
+#[test]
+fn post_ephemeral_json() {
+    let message = Message::new("Only you can see this");
+    let json = serde_json::to_string(&PostEphemeral {
+        channel: "C123",
+        user: "U123",
+        message: &message,
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        r#"{"channel":"C123","user":"U123","text":"Only you can see this"}"#
+    );
+}
@@ -0,0 +1,133 @@
+/// Error returned by a Slack Incoming Webhook
+///
+/// Other than the Web API, webhooks respond with an HTTP error status and a plain text error code.
+///
+/// Documentation: <https://api.slack.com/messaging/webhooks#handling_errors>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    /// `invalid_payload`: The message could not be understood. Check the JSON, blocks and attachments.
+    InvalidPayload,
+
+    /// `no_text`: The message has neither text nor blocks nor attachments.
+    NoText,
+
+    /// `too_many_attachments`: More than 100 attachments were given.
+    TooManyAttachments,
+
+    /// `user_not_found`: The user the webhook posts to does not exist anymore.
+    UserNotFound,
+
+    /// `channel_not_found`: The channel the webhook posts to does not exist anymore.
+    ChannelNotFound,
+
+    /// `channel_is_archived`: The channel the webhook posts to was archived.
+    ChannelIsArchived,
+
+    /// `action_prohibited`: An admin restricted posting to the channel.
+    ActionProhibited,
+
+    /// `posting_to_general_channel_denied`: Only admins can post in the general channel.
+    PostingToGeneralChannelDenied,
+
+    /// `no_service`: The webhook was disabled, removed or is invalid.
+    NoService,
+
+    /// `no_service_id`: The webhook URL is incomplete.
+    NoServiceId,
+
+    /// `no_team`: The workspace of the webhook does not exist anymore.
+    NoTeam,
+
+    /// `team_disabled`: The workspace of the webhook was disabled.
+    TeamDisabled,
+
+    /// `invalid_token`: The token in the webhook URL is invalid.
+    InvalidToken,
+
+    /// Any other response which was not successful
+    Other { status: u16, body: String },
+}
+
+impl WebhookError {
+    #[must_use]
+    pub fn from_response(status: u16, body: &str) -> Self {
+        match body.trim() {
+            "invalid_payload" => Self::InvalidPayload,
+            "no_text" => Self::NoText,
+            "too_many_attachments" => Self::TooManyAttachments,
+            "user_not_found" => Self::UserNotFound,
+            "channel_not_found" => Self::ChannelNotFound,
+            "channel_is_archived" => Self::ChannelIsArchived,
+            "action_prohibited" => Self::ActionProhibited,
+            "posting_to_general_channel_denied" => Self::PostingToGeneralChannelDenied,
+            "no_service" => Self::NoService,
+            "no_service_id" => Self::NoServiceId,
+            "no_team" => Self::NoTeam,
+            "team_disabled" => Self::TeamDisabled,
+            "invalid_token" => Self::InvalidToken,
+            body => Self::Other {
+                status,
+                body: body.to_owned(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::InvalidPayload => "the payload is invalid (invalid_payload)",
+            Self::NoText => "the message has no text (no_text)",
+            Self::TooManyAttachments => {
+                "the message has too many attachments (too_many_attachments)"
+            }
+            Self::UserNotFound => "the user does not exist (user_not_found)",
+            Self::ChannelNotFound => "the channel does not exist (channel_not_found)",
+            Self::ChannelIsArchived => "the channel is archived (channel_is_archived)",
+            Self::ActionProhibited => "posting to the channel is restricted (action_prohibited)",
+            Self::PostingToGeneralChannelDenied => {
+                "only admins can post to the general channel (posting_to_general_channel_denied)"
+            }
+            Self::NoService => "the webhook is disabled or removed (no_service)",
+            Self::NoServiceId => "the webhook URL is incomplete (no_service_id)",
+            Self::NoTeam => "the workspace does not exist (no_team)",
+            Self::TeamDisabled => "the workspace is disabled (team_disabled)",
+            Self::InvalidToken => "the webhook token is invalid (invalid_token)",
+            Self::Other { status, body } => {
+                return write!(
+                    fmt,
+                    "Slack webhook responded with HTTP status {status}: {body}"
+                );
+            }
+        };
+        write!(fmt, "Slack webhook error: {description}")
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+#[test]
+fn known_code() {
+    let error = WebhookError::from_response(410, "channel_is_archived");
+    assert_eq!(error, WebhookError::ChannelIsArchived);
+    assert_eq!(
+        error.to_string(),
+        "Slack webhook error: the channel is archived (channel_is_archived)"
+    );
+}
+
+#[test]
+fn unknown_body() {
+    let error = WebhookError::from_response(502, "Bad Gateway\n");
+    assert_eq!(
+        error,
+        WebhookError::Other {
+            status: 502,
+            body: "Bad Gateway".to_owned()
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Slack webhook responded with HTTP status 502: Bad Gateway"
+    );
+}
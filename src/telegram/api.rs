@@ -0,0 +1,149 @@
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use std::borrow::Cow;
+
+use serde::de::DeserializeOwned;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use url::Url;
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use super::generate_url;
+
+/// Error returned by the Telegram Bot API
+///
+/// Documentation: <https://core.telegram.org/bots/api#making-requests>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub error_code: u16,
+    pub description: String,
+    pub parameters: ResponseParameters,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "Telegram API error {}: {}",
+            self.error_code, self.description
+        )
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Describes why a request was unsuccessful
+///
+/// Documentation: <https://core.telegram.org/bots/api#responseparameters>
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+pub struct ResponseParameters {
+    /// The group has been migrated to a supergroup with the specified identifier
+    pub migrate_to_chat_id: Option<i64>,
+
+    /// Flood control was exceeded. The number of seconds left to wait before the request can be repeated.
+    pub retry_after: Option<u64>,
+}
+
+#[derive(serde::Deserialize)]
+struct Response<T> {
+    ok: bool,
+    result: Option<T>,
+    #[serde(default)]
+    error_code: u16,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    parameters: ResponseParameters,
+}
+
+fn parse_response<T: DeserializeOwned>(status: u16, body: &str) -> anyhow::Result<T> {
+    use anyhow::Context as _;
+
+    let response = serde_json::from_str::<Response<T>>(body).with_context(|| {
+        format!("Telegram API responded with HTTP status {status} and an unexpected body")
+    })?;
+    if response.ok {
+        response
+            .result
+            .context("Telegram API response is missing the result")
+    } else {
+        Err(ApiError {
+            error_code: response.error_code,
+            description: response.description,
+            parameters: response.parameters,
+        }
+        .into())
+    }
+}
+
+/// Call a Bot API method via [`ureq`] and parse its result.
+#[cfg(feature = "ureq")]
+pub fn call_ureq<T: DeserializeOwned>(
+    api_url: &Url,
+    bot_token: &str,
+    method: &str,
+    form: &[(&str, Cow<'_, str>)],
+) -> anyhow::Result<T> {
+    let mut response = ureq::post(&generate_url(api_url, bot_token, method))
+        .header(ureq::http::header::USER_AGENT, crate::USER_AGENT_UREQ)
+        .config()
+        .http_status_as_error(false)
+        .build()
+        .send_form(form.iter().map(|(key, value)| (*key, value.as_ref())))?;
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string()?;
+    parse_response(status, &body)
+}
+
+#[cfg(feature = "reqwest")]
+pub fn reqwest_client() -> reqwest::Result<reqwest::Client> {
+    reqwest::ClientBuilder::new()
+        .user_agent(crate::USER_AGENT_REQWEST)
+        .build()
+}
+
+/// Call a Bot API method via [`reqwest`] and parse its result.
+#[cfg(feature = "reqwest")]
+pub async fn call_reqwest<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_url: &Url,
+    bot_token: &str,
+    method: &str,
+    form: &[(&str, Cow<'_, str>)],
+) -> anyhow::Result<T> {
+    let response = client
+        .post(generate_url(api_url, bot_token, method))
+        .form(form)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+    let status = response.status().as_u16();
+    let body = response.text().await.map_err(reqwest::Error::without_url)?;
+    parse_response(status, &body)
+}
+
+#[test]
+fn parse_ok() {
+    let result = parse_response::<bool>(200, r#"{"ok":true,"result":true}"#).unwrap();
+    assert!(result);
+}
+
+#[test]
+fn parse_api_error() {
+    let body = r#"{"ok":false,"error_code":400,"description":"Bad Request: group chat was upgraded to a supergroup chat","parameters":{"migrate_to_chat_id":-1001234567890}}"#;
+    let error = parse_response::<bool>(400, body).unwrap_err();
+    let error = error.downcast::<ApiError>().unwrap();
+    assert_eq!(error.error_code, 400);
+    assert_eq!(
+        error.parameters.migrate_to_chat_id,
+        Some(-1_001_234_567_890)
+    );
+    assert_eq!(
+        error.to_string(),
+        "Telegram API error 400: Bad Request: group chat was upgraded to a supergroup chat"
+    );
+}
+
+#[test]
+#[should_panic = "HTTP status 502"]
+fn parse_not_json() {
+    parse_response::<bool>(502, "<html>Bad Gateway</html>").unwrap();
+}
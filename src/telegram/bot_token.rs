@@ -0,0 +1,113 @@
+/// Bot Token from `@BotFather` in Telegram
+///
+/// The format is validated on parsing: `<bot id>:<35 characters>`.
+/// The secret part is not shown in the [`Debug`] output.
+///
+/// ```
+/// use pling::TelegramBotToken;
+///
+/// let token = "123456789:ABCdefGHIjklMNOpqrSTUvwxYZ0123456-_".parse::<TelegramBotToken>()?;
+/// assert_eq!(token.bot_id(), 123_456_789);
+/// assert!("123456789:ABC".parse::<TelegramBotToken>().is_err());
+/// # Ok::<(), &str>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BotToken {
+    token: String,
+    bot_id: u64,
+}
+
+/// Length of the secret part of the token after the `:`
+const SECRET_LENGTH: usize = 35;
+
+impl core::str::FromStr for BotToken {
+    type Err = &'static str;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (bot_id, secret) = str
+            .split_once(':')
+            .ok_or("Telegram bot token needs to be in the format <bot id>:<secret>")?;
+        if bot_id.is_empty() || !bot_id.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err("Telegram bot token needs to start with the numeric bot id");
+        }
+        let bot_id = bot_id
+            .parse()
+            .map_err(|_| "Telegram bot token contains a bot id which is too large")?;
+        if secret.len() != SECRET_LENGTH {
+            return Err("Telegram bot token needs 35 characters after the colon");
+        }
+        if !secret
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-')
+        {
+            return Err("Telegram bot token contains invalid characters after the colon");
+        }
+        Ok(Self {
+            token: str.to_owned(),
+            bot_id,
+        })
+    }
+}
+
+impl core::fmt::Debug for BotToken {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "BotToken({}:***)", self.bot_id)
+    }
+}
+
+impl BotToken {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.token
+    }
+
+    /// User ID of the bot which is the part before the `:`
+    #[must_use]
+    pub const fn bot_id(&self) -> u64 {
+        self.bot_id
+    }
+}
+
+#[cfg(test)]
+pub const TEST_TOKEN: &str = "123456:ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi";
+
+#[test]
+fn can_parse() {
+    let token = TEST_TOKEN.parse::<BotToken>().unwrap();
+    assert_eq!(token.as_str(), TEST_TOKEN);
+    assert_eq!(token.bot_id(), 123_456);
+}
+
+#[test]
+fn debug_hides_secret() {
+    let token = TEST_TOKEN.parse::<BotToken>().unwrap();
+    assert_eq!(format!("{token:?}"), "BotToken(123456:***)");
+}
+
+#[test]
+#[should_panic = "format <bot id>:<secret>"]
+fn no_colon() {
+    let result = "123456ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi".parse::<BotToken>();
+    result.unwrap();
+}
+
+#[test]
+#[should_panic = "numeric bot id"]
+fn bot_id_not_numeric() {
+    let result = "bot:ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi".parse::<BotToken>();
+    result.unwrap();
+}
+
+#[test]
+#[should_panic = "35 characters"]
+fn secret_too_short() {
+    let result = "123456:ABC".parse::<BotToken>();
+    result.unwrap();
+}
+
+#[test]
+#[should_panic = "invalid characters"]
+fn secret_invalid_characters() {
+    let result = "123456:ABCDEFGHIJKLMNOPQRSTUVWXYZabcd fghi".parse::<BotToken>();
+    result.unwrap();
+}
@@ -0,0 +1,261 @@
+use super::long_text::utf16_len;
+
+/// Special entity in a text message like a bold text or a link
+///
+/// Offset and length are counted in UTF-16 code units like Telegram does.
+///
+/// Documentation: <https://core.telegram.org/bots/api#messageentity>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MessageEntity {
+    #[serde(flatten)]
+    pub kind: EntityKind,
+
+    /// Offset in UTF-16 code units to the start of the entity
+    pub offset: usize,
+
+    /// Length of the entity in UTF-16 code units
+    pub length: usize,
+}
+
+/// Type of a [`MessageEntity`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EntityKind {
+    /// `@username`
+    Mention,
+    /// `#hashtag`
+    Hashtag,
+    /// `$USD`
+    Cashtag,
+    /// `/start@jobs_bot`
+    BotCommand,
+    /// `https://telegram.org`
+    Url,
+    /// `do-not-reply@telegram.org`
+    Email,
+    /// `+1-212-555-0123`
+    PhoneNumber,
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Spoiler,
+    Blockquote,
+    /// Collapsed-by-default block quotation
+    ExpandableBlockquote,
+    /// Monowidth string
+    Code,
+    /// Monowidth block
+    Pre {
+        /// Programming language of the entity text
+        #[serde(skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+    },
+    /// Clickable text URL
+    TextLink {
+        url: String,
+    },
+    /// Inline custom emoji sticker
+    CustomEmoji {
+        custom_emoji_id: String,
+    },
+}
+
+/// Plain text with [`MessageEntity`]s for formatting.
+///
+/// Other than with a [`ParseMode`](super::ParseMode) the text does not need any escaping.
+///
+/// ```
+/// use pling::TelegramEntityText;
+///
+/// let text = TelegramEntityText::new()
+///     .bold("Build failed")
+///     .text(" for <main> in ")
+///     .code("src/main.rs")
+///     .text(" 😱 ")
+///     .text_link("Logs", "https://example.com/logs");
+/// assert_eq!(text.text, "Build failed for <main> in src/main.rs 😱 Logs");
+/// assert_eq!(text.entities.len(), 3);
+/// assert_eq!(text.entities[2].offset, 42);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct EntityText {
+    pub text: String,
+    pub entities: Vec<MessageEntity>,
+}
+
+impl EntityText {
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// Plain text without formatting
+    pub fn text(mut self, text: &str) -> Self {
+        self.text += text;
+        self
+    }
+
+    /// Add the text formatted with the given entity kind
+    pub fn entity(mut self, kind: EntityKind, text: &str) -> Self {
+        let offset = utf16_len(&self.text);
+        let length = utf16_len(text);
+        self.text += text;
+        if length > 0 {
+            self.entities.push(MessageEntity {
+                kind,
+                offset,
+                length,
+            });
+        }
+        self
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.entity(EntityKind::Bold, text)
+    }
+
+    pub fn italic(self, text: &str) -> Self {
+        self.entity(EntityKind::Italic, text)
+    }
+
+    pub fn underline(self, text: &str) -> Self {
+        self.entity(EntityKind::Underline, text)
+    }
+
+    pub fn strikethrough(self, text: &str) -> Self {
+        self.entity(EntityKind::Strikethrough, text)
+    }
+
+    /// Text which is hidden until tapped
+    pub fn spoiler(self, text: &str) -> Self {
+        self.entity(EntityKind::Spoiler, text)
+    }
+
+    /// Inline monospace text
+    pub fn code(self, code: &str) -> Self {
+        self.entity(EntityKind::Code, code)
+    }
+
+    /// Pre-formatted code block with an optional programming language
+    pub fn pre(self, code: &str, language: Option<&str>) -> Self {
+        let language = language.map(ToOwned::to_owned);
+        self.entity(EntityKind::Pre { language }, code)
+    }
+
+    /// Link with the given text to the URL
+    ///
+    /// Besides `http(s)` URLs, Telegram also supports `tg://user?id=<user_id>` to mention users.
+    pub fn text_link(self, text: &str, url: &str) -> Self {
+        let url = url.to_owned();
+        self.entity(EntityKind::TextLink { url }, text)
+    }
+
+    /// Mention a user or channel by its username. The `@` is added when missing.
+    pub fn mention(self, username: &str) -> Self {
+        let username = if username.starts_with('@') {
+            username.to_owned()
+        } else {
+            format!("@{username}")
+        };
+        self.entity(EntityKind::Mention, &username)
+    }
+
+    #[must_use]
+    pub(crate) fn entities_json(&self) -> String {
+        serde_json::to_string(&self.entities).expect("MessageEntity should always be serializable")
+    }
+}
+
+impl From<&str> for EntityText {
+    fn from(text: &str) -> Self {
+        Self::new().text(text)
+    }
+}
+
+impl From<String> for EntityText {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            entities: Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn offsets_ascii() {
+    let text = EntityText::new().text("Hello ").bold("World").text("!");
+    assert_eq!(text.text, "Hello World!");
+    assert_eq!(
+        text.entities,
+        [MessageEntity {
+            kind: EntityKind::Bold,
+            offset: 6,
+            length: 5,
+        }]
+    );
+}
+
+#[test]
+fn offsets_emoji() {
+    let text = EntityText::new().text("😀👍 ").italic("ok 🎉");
+    assert_eq!(text.entities[0].offset, 5);
+    assert_eq!(text.entities[0].length, 5);
+}
+
+#[test]
+fn offsets_non_latin() {
+    let text = EntityText::new()
+        .text("Привет ")
+        .bold("мир")
+        .text(" 你好 ")
+        .code("世界");
+    assert_eq!(text.entities[0].offset, 7);
+    assert_eq!(text.entities[0].length, 3);
+    assert_eq!(text.entities[1].offset, 14);
+    assert_eq!(text.entities[1].length, 2);
+}
+
+#[test]
+fn empty_entity_is_skipped() {
+    let text = EntityText::new().bold("");
+    assert_eq!(text.entities, []);
+}
+
+#[test]
+fn mention_adds_at() {
+    let text = EntityText::new()
+        .mention("EdJoPaTo")
+        .text(" ")
+        .mention("@pling");
+    assert_eq!(text.text, "@EdJoPaTo @pling");
+    assert_eq!(text.entities[1].offset, 10);
+    assert_eq!(text.entities[1].length, 6);
+}
+
+#[test]
+fn entities_json() {
+    let text = EntityText::new()
+        .bold("a")
+        .pre("b", Some("rust"))
+        .pre("c", None)
+        .text_link("d", "https://example.com/");
+    assert_eq!(
+        text.entities_json(),
+        r#"[{"type":"bold","offset":0,"length":1},{"type":"pre","language":"rust","offset":1,"length":1},{"type":"pre","offset":2,"length":1},{"type":"text_link","url":"https://example.com/","offset":3,"length":1}]"#
+    );
+}
+
+#[test]
+fn entities_json_snake_case() {
+    let text = EntityText::new()
+        .entity(EntityKind::BotCommand, "/start")
+        .entity(EntityKind::ExpandableBlockquote, "quote");
+    assert_eq!(
+        text.entities_json(),
+        r#"[{"type":"bot_command","offset":0,"length":6},{"type":"expandable_blockquote","offset":6,"length":5}]"#
+    );
+}
@@ -0,0 +1,181 @@
+//! Escaping of user provided text for the different [`ParseMode`](super::ParseMode)s
+//!
+//! Documentation: <https://core.telegram.org/bots/api#formatting-options>
+
+/// Characters which need to be escaped in `MarkdownV2` outside of code and links
+const MARKDOWN_V2_SPECIAL: &[char] = &[
+    '\\', '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!',
+];
+
+/// Characters which need to be escaped in the deprecated `Markdown` outside of entities
+const MARKDOWN_SPECIAL: &[char] = &['_', '*', '`', '['];
+
+fn escape_with_backslash(text: &str, special: &[char]) -> String {
+    let mut result = String::with_capacity(text.len());
+    for char in text.chars() {
+        if special.contains(&char) {
+            result.push('\\');
+        }
+        result.push(char);
+    }
+    result
+}
+
+/// Escape `<`, `>` and `&` for HTML text.
+///
+/// `"` is escaped too, so the result can also be used as an attribute value.
+#[must_use]
+pub fn html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '<' => result += "&lt;",
+            '>' => result += "&gt;",
+            '&' => result += "&amp;",
+            '"' => result += "&quot;",
+            _ => result.push(char),
+        }
+    }
+    result
+}
+
+/// Escape all special characters for `MarkdownV2` text outside of code and links.
+#[must_use]
+pub fn markdown_v2(text: &str) -> String {
+    escape_with_backslash(text, MARKDOWN_V2_SPECIAL)
+}
+
+/// Escape `` ` `` and `\` for the content of `MarkdownV2` `pre` and `code` entities.
+#[must_use]
+pub fn markdown_v2_code(text: &str) -> String {
+    escape_with_backslash(text, &['\\', '`'])
+}
+
+/// Escape `)` and `\` for the URL part of a `MarkdownV2` link.
+#[must_use]
+pub fn markdown_v2_url(url: &str) -> String {
+    escape_with_backslash(url, &['\\', ')'])
+}
+
+/// Escape special characters for the deprecated `Markdown` outside of entities.
+///
+/// Escaping inside of entities is not possible with this parse mode, use [`markdown_entity`] there.
+#[must_use]
+pub fn markdown(text: &str) -> String {
+    escape_with_backslash(text, MARKDOWN_SPECIAL)
+}
+
+/// Format the text as an entity of the deprecated `Markdown`.
+///
+/// As escaping inside of entities is not possible, the entity is closed before each special character,
+/// which is then escaped, and reopened afterwards: `snake_case` in bold becomes `*snake*\_*case*`.
+/// `]` ends the entity too, as it would end the text of a link, but needs no escaping outside of it.
+#[must_use]
+pub fn markdown_entity(text: &str, entity: impl Fn(&str) -> String) -> String {
+    markdown_entity_around(text, &['_', '*', '`', '[', ']'], entity)
+}
+
+/// Format the text as a `code` or `pre` entity of the deprecated `Markdown`.
+///
+/// Only backticks end these entities, so they are closed before each backtick, which is then escaped, and reopened afterwards.
+#[must_use]
+pub fn markdown_code_entity(text: &str, entity: impl Fn(&str) -> String) -> String {
+    markdown_entity_around(text, &['`'], entity)
+}
+
+fn markdown_entity_around(text: &str, special: &[char], entity: impl Fn(&str) -> String) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest
+            .find(|char: char| special.contains(&char))
+            .unwrap_or(rest.len());
+        let (run, after) = rest.split_at(end);
+        if !run.is_empty() {
+            result += &entity(run);
+        }
+        let mut chars = after.chars();
+        if let Some(special) = chars.next() {
+            if special != ']' {
+                result.push('\\');
+            }
+            result.push(special);
+        }
+        rest = chars.as_str();
+    }
+    result
+}
+
+#[test]
+fn html_escapes_special() {
+    assert_eq!(html("a < b && c > d"), "a &lt; b &amp;&amp; c &gt; d");
+}
+
+#[test]
+fn html_escapes_quotes() {
+    assert_eq!(html(r#"say "hi""#), "say &quot;hi&quot;");
+}
+
+#[test]
+fn html_keeps_unicode() {
+    assert_eq!(html("Grüße 😀"), "Grüße 😀");
+}
+
+#[test]
+fn markdown_v2_escapes_every_special() {
+    let result = markdown_v2(r"_*[]()~`>#+-=|{}.!\");
+    assert_eq!(result, r"\_\*\[\]\(\)\~\`\>\#\+\-\=\|\{\}\.\!\\");
+}
+
+#[test]
+fn markdown_v2_keeps_normal_text() {
+    assert_eq!(markdown_v2("Hello World 😀 äöü"), "Hello World 😀 äöü");
+}
+
+#[test]
+fn markdown_v2_stack_trace() {
+    let result = markdown_v2("Error: thread 'main' panicked at src/main.rs:4:5 (exit code 101)!");
+    assert_eq!(
+        result,
+        r"Error: thread 'main' panicked at src/main\.rs:4:5 \(exit code 101\)\!"
+    );
+}
+
+#[test]
+fn markdown_v2_code_escapes_only_backtick_and_backslash() {
+    let result = markdown_v2_code(r"let a = `b`.c(\d);");
+    assert_eq!(result, r"let a = \`b\`.c(\\d);");
+}
+
+#[test]
+fn markdown_v2_url_escapes_parenthesis() {
+    let result = markdown_v2_url("https://en.wikipedia.org/wiki/Rust_(programming_language)");
+    assert_eq!(
+        result,
+        r"https://en.wikipedia.org/wiki/Rust_(programming_language\)"
+    );
+}
+
+#[test]
+fn markdown_escapes_special() {
+    assert_eq!(
+        markdown("snake_case *[x]* `y`"),
+        r"snake\_case \*\[x]\* \`y\`"
+    );
+}
+
+#[test]
+fn markdown_entity_is_closed_around_special() {
+    let bold = |text: &str| format!("*{text}*");
+    assert_eq!(markdown_entity("snake_case", bold), r"*snake*\_*case*");
+    assert_eq!(markdown_entity("2*2=4", bold), r"*2*\**2=4*");
+    assert_eq!(markdown_entity("_a_", bold), r"\_*a*\_");
+    assert_eq!(markdown_entity("plain", bold), "*plain*");
+}
+
+#[test]
+fn markdown_code_entity_is_closed_around_backtick() {
+    let code = |text: &str| format!("`{text}`");
+    assert_eq!(markdown_code_entity("a`b", code), r"`a`\``b`");
+    assert_eq!(markdown_code_entity("snake_case", code), "`snake_case`");
+}
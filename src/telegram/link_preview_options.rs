@@ -0,0 +1,69 @@
+use url::Url;
+
+/// Options used for link preview generation
+///
+/// Documentation: <https://core.telegram.org/bots/api#linkpreviewoptions>
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct LinkPreviewOptions {
+    /// Disable the link preview
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub is_disabled: bool,
+
+    /// URL to use for the link preview.
+    ///
+    /// When not set the first URL found in the message text will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Url>,
+
+    /// Shrink the media in the link preview.
+    ///
+    /// Ignored when the URL isn't explicitly specified or media size change isn't supported for the preview.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub prefer_small_media: bool,
+
+    /// Enlarge the media in the link preview.
+    ///
+    /// Ignored when the URL isn't explicitly specified or media size change isn't supported for the preview.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub prefer_large_media: bool,
+
+    /// Show the link preview above the message text instead of below it
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub show_above_text: bool,
+}
+
+impl LinkPreviewOptions {
+    #[must_use]
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("LinkPreviewOptions should always be serializable")
+    }
+}
+
+#[test]
+fn json_default_is_empty_object() {
+    assert_eq!(LinkPreviewOptions::default().to_json(), "{}");
+}
+
+#[test]
+fn json_disabled() {
+    let options = LinkPreviewOptions {
+        is_disabled: true,
+        ..LinkPreviewOptions::default()
+    };
+    assert_eq!(options.to_json(), r#"{"is_disabled":true}"#);
+}
+
+#[test]
+fn json_url_above_text() {
+    let options = LinkPreviewOptions {
+        url: Some(Url::parse("https://example.com/").unwrap()),
+        prefer_large_media: true,
+        show_above_text: true,
+        ..LinkPreviewOptions::default()
+    };
+    assert_eq!(
+        options.to_json(),
+        r#"{"url":"https://example.com/","prefer_large_media":true,"show_above_text":true}"#
+    );
+}
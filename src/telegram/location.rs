@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+use super::Form;
+
+/// Point on the map
+///
+/// Documentation: <https://core.telegram.org/bots/api#sendlocation>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+
+    /// The radius of uncertainty for the location, measured in meters; 0-1500
+    pub horizontal_accuracy: Option<f64>,
+}
+
+impl Location {
+    #[must_use]
+    pub const fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            horizontal_accuracy: None,
+        }
+    }
+
+    pub(crate) fn append_to(&self, form: &mut Form<'_>) {
+        form.push(("latitude", Cow::Owned(self.latitude.to_string())));
+        form.push(("longitude", Cow::Owned(self.longitude.to_string())));
+        if let Some(accuracy) = self.horizontal_accuracy {
+            form.push(("horizontal_accuracy", Cow::Owned(accuracy.to_string())));
+        }
+    }
+}
+
+/// Named place on the map like a building or a device site
+///
+/// Documentation: <https://core.telegram.org/bots/api#sendvenue>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Venue {
+    pub latitude: f64,
+    pub longitude: f64,
+
+    /// Name of the venue
+    pub title: String,
+    pub address: String,
+
+    /// Google Places identifier of the venue
+    pub google_place_id: Option<String>,
+}
+
+impl Venue {
+    #[must_use]
+    pub const fn new(latitude: f64, longitude: f64, title: String, address: String) -> Self {
+        Self {
+            latitude,
+            longitude,
+            title,
+            address,
+            google_place_id: None,
+        }
+    }
+
+    pub(crate) fn append_to<'a>(&'a self, form: &mut Form<'a>) {
+        form.push(("latitude", Cow::Owned(self.latitude.to_string())));
+        form.push(("longitude", Cow::Owned(self.longitude.to_string())));
+        form.push(("title", Cow::Borrowed(&self.title)));
+        form.push(("address", Cow::Borrowed(&self.address)));
+        if let Some(id) = &self.google_place_id {
+            form.push(("google_place_id", Cow::Borrowed(id)));
+        }
+    }
+}
+
+#[test]
+fn location_form() {
+    let location = Location {
+        horizontal_accuracy: Some(12.5),
+        ..Location::new(53.55, 9.993)
+    };
+    let mut form = Vec::new();
+    location.append_to(&mut form);
+    assert_eq!(
+        form,
+        [
+            ("latitude", Cow::Borrowed("53.55")),
+            ("longitude", Cow::Borrowed("9.993")),
+            ("horizontal_accuracy", Cow::Borrowed("12.5")),
+        ]
+    );
+}
+
+#[test]
+fn venue_form() {
+    let venue = Venue::new(
+        53.55,
+        9.993,
+        "Sensor 42".to_owned(),
+        "Rathausmarkt 1, Hamburg".to_owned(),
+    );
+    let mut form = Vec::new();
+    venue.append_to(&mut form);
+    assert_eq!(
+        form,
+        [
+            ("latitude", Cow::Borrowed("53.55")),
+            ("longitude", Cow::Borrowed("9.993")),
+            ("title", Cow::Borrowed("Sensor 42")),
+            ("address", Cow::Borrowed("Rathausmarkt 1, Hamburg")),
+        ]
+    );
+}
@@ -0,0 +1,761 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use super::{EntityText, MessageEntity, ParseMode};
+
+/// Maximum length of a message text in UTF-16 code units
+///
+/// Documentation: <https://core.telegram.org/bots/api#sendmessage>
+pub const MAX_TEXT_LENGTH: usize = 4096;
+
+/// What to do with texts longer than [`MAX_TEXT_LENGTH`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum LongText {
+    /// Split the text into multiple messages, preferably at line boundaries
+    #[default]
+    Split,
+
+    /// Only send the beginning of the text followed by a `…(truncated)` marker
+    Truncate,
+}
+
+impl LongText {
+    /// Fit the text into messages of at most [`MAX_TEXT_LENGTH`].
+    ///
+    /// Formatting entities of the `parse_mode` are closed at the end of a message and reopened at the start of the next one.
+    #[must_use]
+    pub(crate) fn apply(self, text: &str, parse_mode: Option<ParseMode>) -> Vec<Cow<'_, str>> {
+        if utf16_len(text) <= MAX_TEXT_LENGTH {
+            return vec![Cow::Borrowed(text)];
+        }
+        match self {
+            Self::Split => split(text, parse_mode, MAX_TEXT_LENGTH)
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+            Self::Truncate => vec![Cow::Owned(truncate(text, parse_mode, MAX_TEXT_LENGTH))],
+        }
+    }
+
+    /// Fit the text into messages of at most [`MAX_TEXT_LENGTH`].
+    ///
+    /// Entities spanning multiple messages are split too.
+    #[must_use]
+    pub(crate) fn apply_entities(self, text: &EntityText) -> Vec<Cow<'_, EntityText>> {
+        if utf16_len(&text.text) <= MAX_TEXT_LENGTH {
+            return vec![Cow::Borrowed(text)];
+        }
+        match self {
+            Self::Split => split_entities(text, MAX_TEXT_LENGTH)
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+            Self::Truncate => {
+                let marker = truncated_marker(None);
+                let mut result = split_entities(text, MAX_TEXT_LENGTH - utf16_len(marker))
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                result.text += marker;
+                vec![Cow::Owned(result)]
+            }
+        }
+    }
+}
+
+/// Length of the text in UTF-16 code units like Telegram counts it
+#[must_use]
+pub fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+#[must_use]
+const fn truncated_marker(parse_mode: Option<ParseMode>) -> &'static str {
+    match parse_mode {
+        Some(ParseMode::MarkdownV2) => "…\\(truncated\\)",
+        _ => "…(truncated)",
+    }
+}
+
+#[must_use]
+fn truncate(text: &str, parse_mode: Option<ParseMode>, max_length: usize) -> String {
+    let marker = truncated_marker(parse_mode);
+    let mut chunks = split(text, parse_mode, max_length - utf16_len(marker)).into_iter();
+    let mut result = chunks.next().unwrap_or_default();
+    if chunks.next().is_some() {
+        result += marker;
+    }
+    result
+}
+
+#[must_use]
+fn split(text: &str, parse_mode: Option<ParseMode>, max_length: usize) -> Vec<String> {
+    let atoms = match parse_mode {
+        None => tokenize_plain(text),
+        Some(ParseMode::HTML) => tokenize_html(text),
+        #[allow(deprecated)]
+        Some(ParseMode::Markdown) => tokenize_markdown(text, false),
+        Some(ParseMode::MarkdownV2) => tokenize_markdown(text, true),
+    };
+    split_atoms(&atoms, max_length)
+}
+
+/// Formatting entity which needs to be closed and reopened when a message is split
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entity<'text> {
+    key: &'text str,
+    reopen: &'text str,
+    close: Cow<'text, str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind<'text> {
+    Text,
+    Space,
+    LineBreak,
+    Open(Entity<'text>),
+    Close(&'text str),
+}
+
+/// Smallest part of the text which must not be split
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Atom<'text> {
+    raw: &'text str,
+    kind: Kind<'text>,
+}
+
+fn char_atom(rest: &str, char: char) -> Atom<'_> {
+    let kind = match char {
+        '\n' => Kind::LineBreak,
+        ' ' => Kind::Space,
+        _ => Kind::Text,
+    };
+    Atom {
+        raw: &rest[..char.len_utf8()],
+        kind,
+    }
+}
+
+fn tokenize_plain(text: &str) -> Vec<Atom<'_>> {
+    text.char_indices()
+        .map(|(index, char)| char_atom(&text[index..], char))
+        .collect()
+}
+
+fn tokenize_html(text: &str) -> Vec<Atom<'_>> {
+    let mut atoms = Vec::new();
+    let mut rest = text;
+    while let Some(char) = rest.chars().next() {
+        let atom = match char {
+            '<' => rest.find('>').map(|end| html_tag(&rest[..=end])),
+            '&' => html_entity(rest).map(|raw| Atom {
+                raw,
+                kind: Kind::Text,
+            }),
+            _ => None,
+        }
+        .unwrap_or_else(|| char_atom(rest, char));
+        rest = &rest[atom.raw.len()..];
+        atoms.push(atom);
+    }
+    atoms
+}
+
+/// Finds an HTML entity supported by Telegram at the start of the text.
+///
+/// These are all numerical entities and the named ones `&lt;`, `&gt;`, `&amp;` and `&quot;`.
+fn html_entity(rest: &str) -> Option<&str> {
+    const NAMED: [&str; 4] = ["&lt;", "&gt;", "&amp;", "&quot;"];
+
+    if let Some(named) = NAMED.into_iter().find(|named| rest.starts_with(named)) {
+        return Some(&rest[..named.len()]);
+    }
+    let number = rest.strip_prefix("&#")?;
+    let (digits, radix) = number
+        .strip_prefix(['x', 'X'])
+        .map_or((number, 10), |hex| (hex, 16));
+    let digits_len = digits
+        .find(|char: char| !char.is_digit(radix))
+        .unwrap_or(digits.len());
+    let end = rest.len() - digits.len() + digits_len;
+    (digits_len > 0 && digits[digits_len..].starts_with(';')).then(|| &rest[..=end])
+}
+
+fn html_tag(tag: &str) -> Atom<'_> {
+    let inner = tag.trim_start_matches('<');
+    let is_closing = inner.starts_with('/');
+    let name = inner
+        .trim_start_matches('/')
+        .split(|char: char| char.is_whitespace() || char == '>' || char == '/')
+        .next()
+        .unwrap_or_default();
+    let kind = if is_closing {
+        Kind::Close(name)
+    } else {
+        Kind::Open(Entity {
+            key: name,
+            reopen: tag,
+            close: Cow::Owned(format!("</{name}>")),
+        })
+    };
+    Atom { raw: tag, kind }
+}
+
+fn tokenize_markdown(text: &str, v2: bool) -> Vec<Atom<'_>> {
+    const PRE: &str = "```";
+    const CODE: &str = "`";
+    const LINK: &str = "[";
+
+    let toggle_markers: &[&str] = if v2 {
+        &["||", "__", "_", "*", "~"]
+    } else {
+        &["_", "*"]
+    };
+
+    let mut atoms = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut link_close: Option<&str> = None;
+    let mut index = 0;
+    while let Some(char) = text[index..].chars().next() {
+        let rest = &text[index..];
+        let innermost = open.last().copied();
+        let atom = if char == '\\' && rest.len() > 1 {
+            let escaped = rest[1..].chars().next().map_or(0, char::len_utf8);
+            Atom {
+                raw: &rest[..=escaped],
+                kind: Kind::Text,
+            }
+        } else if let Some(close) = link_close.filter(|close| rest.starts_with(close)) {
+            link_close = None;
+            open.retain(|key| *key != LINK);
+            Atom {
+                raw: close,
+                kind: Kind::Close(LINK),
+            }
+        } else if let Some(marker) = innermost.filter(|key| *key == PRE || *key == CODE) {
+            if rest.starts_with(marker) {
+                open.pop();
+                Atom {
+                    raw: &rest[..marker.len()],
+                    kind: Kind::Close(marker),
+                }
+            } else {
+                char_atom(rest, char)
+            }
+        } else if let Some(marker) = [PRE, CODE]
+            .into_iter()
+            .find(|marker| rest.starts_with(marker))
+        {
+            // The language of a pre block is part of the opening
+            let opening_len = if marker == PRE {
+                rest[PRE.len()..]
+                    .split_once('\n')
+                    .filter(|(language, _)| !language.contains(char::is_whitespace))
+                    .map_or(PRE.len(), |(language, _)| PRE.len() + language.len() + 1)
+            } else {
+                marker.len()
+            };
+            open.push(marker);
+            markdown_open(marker, &rest[..opening_len], marker)
+        } else if let Some(marker) = toggle_markers
+            .iter()
+            .find(|marker| rest.starts_with(*marker))
+        {
+            let raw = &rest[..marker.len()];
+            if let Some(position) = open.iter().rposition(|key| key == marker) {
+                open.remove(position);
+                Atom {
+                    raw,
+                    kind: Kind::Close(marker),
+                }
+            } else {
+                open.push(marker);
+                markdown_open(marker, raw, marker)
+            }
+        } else if let Some((opening, close)) = markdown_link(rest).filter(|_| link_close.is_none())
+        {
+            link_close = Some(close);
+            open.push(LINK);
+            markdown_open(LINK, opening, close)
+        } else {
+            char_atom(rest, char)
+        };
+        index += atom.raw.len();
+        atoms.push(atom);
+    }
+    atoms
+}
+
+const fn markdown_open<'text>(
+    key: &'text str,
+    opening: &'text str,
+    close: &'text str,
+) -> Atom<'text> {
+    Atom {
+        raw: opening,
+        kind: Kind::Open(Entity {
+            key,
+            reopen: opening,
+            close: Cow::Borrowed(close),
+        }),
+    }
+}
+
+/// Finds the opening (`[` or `![`) and the closing (`](url)`) of a Markdown link at the start of the text
+fn markdown_link(rest: &str) -> Option<(&str, &str)> {
+    let opening_len = if rest.starts_with('[') {
+        1
+    } else if rest.starts_with("![") {
+        2
+    } else {
+        return None;
+    };
+    let mut close_start = None;
+    let mut chars = rest.char_indices().skip(opening_len);
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '\\' => {
+                chars.next();
+            }
+            ']' if close_start.is_none() => {
+                if !rest[index + 1..].starts_with('(') {
+                    return None;
+                }
+                close_start = Some(index);
+                chars.next();
+            }
+            ')' => {
+                if let Some(close_start) = close_start {
+                    return Some((&rest[..opening_len], &rest[close_start..=index]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Track the entities opened and closed by the atom and return the closed one
+fn apply<'atoms, 'text>(
+    open: &mut Vec<&'atoms Entity<'text>>,
+    atom: &'atoms Atom<'text>,
+) -> Option<&'atoms Entity<'text>> {
+    match &atom.kind {
+        Kind::Open(entity) => {
+            open.push(entity);
+            None
+        }
+        Kind::Close(key) => open
+            .iter()
+            .rposition(|entity| entity.key == *key)
+            .map(|position| open.remove(position)),
+        Kind::Text | Kind::Space | Kind::LineBreak => None,
+    }
+}
+
+#[must_use]
+fn contains(entities: &[&Entity<'_>], entity: &Entity<'_>) -> bool {
+    entities
+        .iter()
+        .any(|contained| std::ptr::eq(*contained, entity))
+}
+
+/// Position to end a chunk at when the text gets too long
+struct Cut<'atoms, 'text> {
+    index: usize,
+    open: Vec<&'atoms Entity<'text>>,
+}
+
+/// Part of the text which fits into one message
+struct Chunk<'atoms, 'text> {
+    reopen: Vec<&'atoms Entity<'text>>,
+    atoms: Range<usize>,
+    /// Atoms closing entities which were not reopened in this chunk
+    skip: Vec<usize>,
+    close: Vec<&'atoms Entity<'text>>,
+}
+
+fn chunk_atoms<'atoms, 'text>(
+    atoms: &'atoms [Atom<'text>],
+    max_length: usize,
+) -> Vec<Chunk<'atoms, 'text>> {
+    let mut chunks = Vec::new();
+    let mut open = Vec::new();
+    let mut index = 0;
+    while index < atoms.len() {
+        let start = index;
+        let mut reopen = open.clone();
+        // Reopened entities left out because they do not leave room for any text
+        let mut dropped = Vec::new();
+        let mut skip = Vec::new();
+        let mut end = None;
+        let mut length = reopen
+            .iter()
+            .map(|entity: &&Entity| utf16_len(entity.reopen))
+            .sum::<usize>();
+        let mut has_content = false;
+        let mut line_break: Option<Cut> = None;
+        let mut space: Option<Cut> = None;
+
+        while let Some(atom) = atoms.get(index) {
+            let mut next_open = open.clone();
+            let skipped =
+                apply(&mut next_open, atom).is_some_and(|closed| contains(&dropped, closed));
+            let atom_length = if skipped { 0 } else { utf16_len(atom.raw) };
+            let closing_length = next_open
+                .iter()
+                .filter(|entity| !contains(&dropped, entity))
+                .map(|entity| utf16_len(&entity.close))
+                .sum::<usize>();
+            let here = || Cut {
+                index,
+                open: open.clone(),
+            };
+            if length + atom_length + closing_length > max_length {
+                if has_content {
+                    let cut = match atom.kind {
+                        Kind::LineBreak => Some(here()),
+                        Kind::Space => line_break.or_else(|| Some(here())),
+                        _ => line_break.or(space),
+                    };
+                    if let Some(cut) = cut {
+                        open = cut.open;
+                        end = Some(cut.index);
+                        index = cut.index + 1;
+                    } else {
+                        end = Some(index);
+                    }
+                    break;
+                }
+                // Without any text in the chunk yet, leave out reopened entities until the atom fits
+                if let Some(entity) = reopen.iter().find(|entity| !contains(&dropped, entity)) {
+                    length -= utf16_len(entity.reopen);
+                    dropped.push(*entity);
+                    continue;
+                }
+            }
+
+            match atom.kind {
+                Kind::LineBreak if has_content => line_break = Some(here()),
+                Kind::Space if has_content => space = Some(here()),
+                _ => {}
+            }
+
+            if skipped {
+                skip.push(index);
+            }
+            length += atom_length;
+            has_content |= matches!(atom.kind, Kind::Text);
+            open = next_open;
+            index += 1;
+        }
+
+        let end_index = end.unwrap_or(index);
+        skip.retain(|skipped| *skipped < end_index);
+        reopen.retain(|entity| !contains(&dropped, entity));
+        chunks.push(Chunk {
+            reopen,
+            atoms: start..end_index,
+            skip,
+            // Only close entities when the text continues in the next chunk
+            close: if end.is_some() {
+                open.iter()
+                    .copied()
+                    .filter(|entity| !contains(&dropped, entity))
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        });
+    }
+    chunks
+}
+
+fn split_entities(text: &EntityText, max_length: usize) -> Vec<EntityText> {
+    let atoms = tokenize_plain(&text.text);
+
+    // Byte and UTF-16 offset of the start of each atom and the end of the text
+    let mut offsets = Vec::with_capacity(atoms.len() + 1);
+    let mut byte = 0;
+    let mut utf16 = 0;
+    offsets.push((byte, utf16));
+    for atom in &atoms {
+        byte += atom.raw.len();
+        utf16 += utf16_len(atom.raw);
+        offsets.push((byte, utf16));
+    }
+
+    chunk_atoms(&atoms, max_length)
+        .into_iter()
+        .map(|chunk| {
+            let (byte_start, start) = offsets[chunk.atoms.start];
+            let (byte_end, end) = offsets[chunk.atoms.end];
+            let entities = text
+                .entities
+                .iter()
+                .filter_map(|entity| {
+                    let entity_start = entity.offset.max(start);
+                    let entity_end = (entity.offset + entity.length).min(end);
+                    (entity_start < entity_end).then(|| MessageEntity {
+                        kind: entity.kind.clone(),
+                        offset: entity_start - start,
+                        length: entity_end - entity_start,
+                    })
+                })
+                .collect();
+            EntityText {
+                text: text.text[byte_start..byte_end].to_owned(),
+                entities,
+            }
+        })
+        .collect()
+}
+
+fn split_atoms(atoms: &[Atom<'_>], max_length: usize) -> Vec<String> {
+    chunk_atoms(atoms, max_length)
+        .into_iter()
+        .map(|chunk| {
+            let mut result = String::new();
+            for entity in chunk.reopen {
+                result += entity.reopen;
+            }
+            for index in chunk.atoms.filter(|index| !chunk.skip.contains(index)) {
+                result += atoms[index].raw;
+            }
+            for entity in chunk.close.iter().rev() {
+                result += &entity.close;
+            }
+            result
+        })
+        .collect()
+}
+
+#[test]
+fn short_text_is_unchanged() {
+    let result = LongText::Split.apply("Hello world", None);
+    assert_eq!(result, ["Hello world"]);
+}
+
+#[test]
+fn counts_utf16_code_units() {
+    assert_eq!(utf16_len("abc"), 3);
+    assert_eq!(utf16_len("äöü"), 3);
+    assert_eq!(utf16_len("😀"), 2);
+    assert_eq!(utf16_len("👨‍👩‍👧"), 8);
+}
+
+#[test]
+fn split_plain_at_line_breaks() {
+    let result = split("first line\nsecond line\nthird", None, 25);
+    assert_eq!(result, ["first line\nsecond line", "third"]);
+}
+
+#[test]
+fn split_plain_at_spaces() {
+    let result = split("some words without line breaks", None, 12);
+    assert_eq!(result, ["some words", "without line", "breaks"]);
+}
+
+#[test]
+fn split_plain_long_word() {
+    let result = split("abcdefghij", None, 4);
+    assert_eq!(result, ["abcd", "efgh", "ij"]);
+}
+
+#[test]
+fn split_emoji_by_utf16_length() {
+    let text = "😀".repeat(3000);
+    let result = LongText::Split.apply(&text, None);
+    assert_eq!(result.len(), 2);
+    for chunk in &result {
+        assert!(utf16_len(chunk) <= MAX_TEXT_LENGTH);
+    }
+    assert_eq!(result.concat(), text);
+}
+
+#[test]
+fn split_long_stack_trace() {
+    let text = (0..500)
+        .map(|index| format!("  at some::function::call_{index} (src/main.rs:{index})"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let result = LongText::Split.apply(&text, None);
+    assert!(result.len() > 1);
+    for chunk in &result {
+        assert!(utf16_len(chunk) <= MAX_TEXT_LENGTH);
+        assert!(chunk.starts_with("  at "));
+    }
+    assert_eq!(result.join("\n"), text);
+}
+
+#[test]
+fn split_html_reopens_tags() {
+    let result = split(
+        "<b>bold <i>both</i>\n<a href=\"https://example.com\">link text</a></b>",
+        Some(ParseMode::HTML),
+        45,
+    );
+    assert_eq!(
+        result,
+        [
+            "<b>bold <i>both</i></b>",
+            "<b><a href=\"https://example.com\">link</a></b>",
+            "<b><a href=\"https://example.com\">text</a></b>",
+        ]
+    );
+}
+
+#[test]
+fn split_html_leaves_out_tags_without_room() {
+    let result = split(
+        "<b>a <a href=\"https://e.com/\">b</a></b>",
+        Some(ParseMode::HTML),
+        33,
+    );
+    assert_eq!(result, ["<b>a</b>", "<a href=\"https://e.com/\">b</a>"]);
+    for chunk in &result {
+        assert!(utf16_len(chunk) <= 33);
+    }
+}
+
+#[test]
+fn split_html_keeps_entities() {
+    let result = split("&lt;&lt;&lt;&lt;", Some(ParseMode::HTML), 10);
+    assert_eq!(result, ["&lt;&lt;", "&lt;&lt;"]);
+}
+
+#[test]
+fn split_html_keeps_tag_with_semicolon() {
+    let result = split(
+        "<a href=\"https://x/?a=1;b=2\">one two</a>",
+        Some(ParseMode::HTML),
+        38,
+    );
+    assert_eq!(
+        result,
+        [
+            "<a href=\"https://x/?a=1;b=2\">one</a>",
+            "<a href=\"https://x/?a=1;b=2\">two</a>",
+        ]
+    );
+}
+
+#[test]
+fn tokenize_html_bounds_entities() {
+    let atoms = tokenize_html("&#x10FFFF; &amp is no entity;");
+    assert_eq!(atoms[0].raw, "&#x10FFFF;");
+    assert_eq!(atoms[2].raw, "&");
+}
+
+#[test]
+fn tokenize_html_only_valid_entities() {
+    let raws = |text| {
+        tokenize_html(text)
+            .into_iter()
+            .map(|atom| atom.raw)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        raws("&quot;&#39;&#X1F600;"),
+        ["&quot;", "&#39;", "&#X1F600;"]
+    );
+    assert_eq!(raws("&a;"), ["&", "a", ";"]);
+    assert_eq!(raws("&#;"), ["&", "#", ";"]);
+    assert_eq!(raws("&#xg;"), ["&", "#", "x", "g", ";"]);
+    assert_eq!(raws("&#12"), ["&", "#", "1", "2"]);
+}
+
+#[test]
+fn split_markdown_v2_reopens_entities() {
+    let result = split(
+        "*bold _italic_\nmore bold\\* still*",
+        Some(ParseMode::MarkdownV2),
+        20,
+    );
+    assert_eq!(result, ["*bold _italic_*", "*more bold\\* still*"]);
+}
+
+#[test]
+fn split_markdown_v2_pre_keeps_language() {
+    let result = split(
+        "```rust\nlet a = 1;\nlet b = 2;\n```",
+        Some(ParseMode::MarkdownV2),
+        25,
+    );
+    assert_eq!(
+        result,
+        ["```rust\nlet a = 1;```", "```rust\nlet b = 2;\n```"]
+    );
+}
+
+#[test]
+fn split_markdown_v2_ignores_markers_in_code() {
+    let result = split("`a_b c*d`", Some(ParseMode::MarkdownV2), 7);
+    assert_eq!(result, ["`a_b`", "`c*d`"]);
+}
+
+#[test]
+fn split_markdown_v2_link_keeps_url() {
+    let result = split(
+        "[some link](https://example.com/\\))",
+        Some(ParseMode::MarkdownV2),
+        30,
+    );
+    assert_eq!(
+        result,
+        [
+            "[some](https://example.com/\\))",
+            "[link](https://example.com/\\))",
+        ]
+    );
+}
+
+#[test]
+fn truncate_plain() {
+    let result = truncate("first line\nsecond line", None, 22);
+    assert_eq!(result, "first line…(truncated)");
+}
+
+#[test]
+fn truncate_markdown_v2_escapes_marker() {
+    let result = truncate("*first line\nsecond line*", Some(ParseMode::MarkdownV2), 30);
+    assert_eq!(result, "*first line*…\\(truncated\\)");
+}
+
+#[test]
+fn truncate_fits_max_length() {
+    let text = "a".repeat(5000);
+    let result = LongText::Truncate.apply(&text, None);
+    assert_eq!(result.len(), 1);
+    assert_eq!(utf16_len(&result[0]), MAX_TEXT_LENGTH);
+    assert!(result[0].ends_with("…(truncated)"));
+}
+
+#[test]
+fn split_entities_clips_entities() {
+    let text = EntityText::new()
+        .text("😀 ")
+        .bold("bold text")
+        .text("\n")
+        .code("code")
+        .text(" end");
+    let result = split_entities(&text, 10);
+    assert_eq!(
+        result,
+        [
+            EntityText::new().text("😀 ").bold("bold"),
+            EntityText::new().bold("text"),
+            EntityText::new().code("code").text(" end"),
+        ]
+    );
+}
+
+#[test]
+fn truncate_entities() {
+    let text = EntityText::new().bold(&"a".repeat(5000));
+    let result = LongText::Truncate.apply_entities(&text);
+    assert_eq!(result.len(), 1);
+    assert_eq!(utf16_len(&result[0].text), MAX_TEXT_LENGTH);
+    assert_eq!(result[0].entities[0].length, MAX_TEXT_LENGTH - 12);
+}
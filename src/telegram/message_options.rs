@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+use super::Form;
+
+/// Further options of a sent message
+///
+/// ```
+/// use pling::TelegramMessageOptions;
+///
+/// let mut options = TelegramMessageOptions::default();
+/// options.message_thread_id = Some(42);
+/// options.protect_content = true;
+/// ```
+///
+/// Documentation: <https://core.telegram.org/bots/api#sendmessage>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MessageOptions {
+    /// Unique identifier of the business connection on behalf of which the message will be sent
+    pub business_connection_id: Option<String>,
+
+    /// Unique identifier of the target message thread (topic) of a forum supergroup
+    pub message_thread_id: Option<i64>,
+
+    /// Protects the contents of the sent message from forwarding and saving
+    pub protect_content: bool,
+
+    /// Allow up to 1000 messages per second, ignoring broadcasting limits for a fee of 0.1 Telegram Stars per message.
+    ///
+    /// The relevant Stars will be withdrawn from the bot's balance.
+    pub allow_paid_broadcast: bool,
+
+    /// Unique identifier of the message effect to be added to the message. Only works in private chats.
+    pub message_effect_id: Option<String>,
+}
+
+impl MessageOptions {
+    pub(crate) fn append_to<'a>(&'a self, form: &mut Form<'a>) {
+        if let Some(id) = &self.business_connection_id {
+            form.push(("business_connection_id", Cow::Borrowed(id)));
+        }
+        if let Some(id) = self.message_thread_id {
+            form.push(("message_thread_id", Cow::Owned(id.to_string())));
+        }
+        if self.protect_content {
+            form.push(("protect_content", Cow::Borrowed("true")));
+        }
+        if self.allow_paid_broadcast {
+            form.push(("allow_paid_broadcast", Cow::Borrowed("true")));
+        }
+        if let Some(id) = &self.message_effect_id {
+            form.push(("message_effect_id", Cow::Borrowed(id)));
+        }
+    }
+}
+
+#[test]
+fn default_appends_nothing() {
+    let options = MessageOptions::default();
+    let mut form = Vec::new();
+    options.append_to(&mut form);
+    assert_eq!(form, []);
+}
+
+#[test]
+fn appends_all() {
+    let options = MessageOptions {
+        business_connection_id: Some("abc".to_owned()),
+        message_thread_id: Some(42),
+        protect_content: true,
+        allow_paid_broadcast: true,
+        message_effect_id: Some("5104841245755180586".to_owned()),
+    };
+    let mut form = Vec::new();
+    options.append_to(&mut form);
+    assert_eq!(
+        form,
+        [
+            ("business_connection_id", Cow::Borrowed("abc")),
+            ("message_thread_id", Cow::Borrowed("42")),
+            ("protect_content", Cow::Borrowed("true")),
+            ("allow_paid_broadcast", Cow::Borrowed("true")),
+            ("message_effect_id", Cow::Borrowed("5104841245755180586")),
+        ]
+    );
+}
@@ -1,8 +1,67 @@
+use std::borrow::Cow;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use std::ops::ControlFlow;
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use std::time::Duration;
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+use anyhow::Context as _;
+use url::Url;
+
+pub use self::api::{ApiError, ResponseParameters};
+pub use self::bot_token::BotToken;
+pub use self::entity::{EntityKind, EntityText, MessageEntity};
+pub use self::link_preview_options::LinkPreviewOptions;
+pub use self::location::{Location, Venue};
+pub use self::long_text::{LongText, MAX_TEXT_LENGTH};
+pub use self::message_options::MessageOptions;
 pub use self::parse_mode::ParseMode;
+pub use self::poll::Poll;
 pub use self::target_chat::TargetChat;
+pub use self::text_builder::TextBuilder;
+pub use self::types::{Chat, ChatType, Message, SeenChats, Sent, User};
+pub use self::updates::{Command, Incoming};
+pub use self::verify::{ChatAccess, Verification};
 
+mod api;
+mod bot_token;
+mod entity;
+mod escape;
+mod link_preview_options;
+mod location;
+mod long_text;
+mod message_options;
 mod parse_mode;
+mod poll;
 mod target_chat;
+mod text_builder;
+mod types;
+mod updates;
+mod verify;
+
+#[cfg(all(test, feature = "ureq"))]
+use crate::fake_api;
+
+type Form<'a> = Vec<(&'static str, Cow<'a, str>)>;
+
+/// How often a request is repeated when the flood control was exceeded
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const MAX_RETRIES: u8 = 3;
+
+/// Seconds a single `getUpdates` request waits for new updates
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const LONG_POLLING_TIMEOUT: &str = "30";
+
+/// Wait before polling again after `getUpdates` failed temporarily
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const POLLING_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Longer waits requested by the flood control are reported as error instead
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Public Telegram Bot API server
+pub const DEFAULT_API_URL: &str = "https://api.telegram.org";
 
 /// Telegram Notification
 ///
@@ -10,106 +69,920 @@ mod target_chat;
 #[derive(Clone, PartialEq, Eq)]
 pub struct Telegram {
     /// Bot Token from `@BotFather` in Telegram
-    pub bot_token: String,
+    pub bot_token: BotToken,
 
-    pub target_chat: TargetChat,
+    /// Chats to send the notification to. Each chat is sent to one after another.
+    pub target_chats: Vec<TargetChat>,
+
+    /// Bot API server to use. Defaults to [`DEFAULT_API_URL`].
+    ///
+    /// Useful for a self-hosted [Bot API server](https://github.com/tdlib/telegram-bot-api) or a mock in tests.
+    pub api_url: Url,
 
     // optional
+    /// Shorthand for [`LinkPreviewOptions::is_disabled`]
     pub disable_web_page_preview: bool,
+    pub link_preview_options: Option<LinkPreviewOptions>,
     pub disable_notification: bool,
     pub parse_mode: Option<ParseMode>,
+
+    /// What to do with texts longer than [`MAX_TEXT_LENGTH`]
+    pub long_text: LongText,
+
+    pub message_options: MessageOptions,
 }
 
 impl Telegram {
     #[must_use]
-    pub const fn new(bot_token: String, target_chat: TargetChat) -> Self {
+    pub fn new(bot_token: BotToken, target_chat: TargetChat) -> Self {
         Self {
             bot_token,
-            target_chat,
+            target_chats: vec![target_chat],
+            api_url: default_api_url(),
             disable_web_page_preview: false,
+            link_preview_options: None,
             disable_notification: false,
             parse_mode: None,
+            long_text: LongText::Split,
+            message_options: MessageOptions::default(),
         }
     }
 
     #[must_use]
-    fn base_form(&self) -> Vec<(&str, &str)> {
-        let mut result = Vec::new();
-        if self.disable_web_page_preview {
-            result.push(("disable_web_page_preview", "true"));
+    fn link_preview_options(&self) -> Option<Cow<'_, LinkPreviewOptions>> {
+        match (&self.link_preview_options, self.disable_web_page_preview) {
+            (None, false) => None,
+            (Some(options), false) => Some(Cow::Borrowed(options)),
+            (options, true) => Some(Cow::Owned(LinkPreviewOptions {
+                is_disabled: true,
+                ..options.clone().unwrap_or_default()
+            })),
         }
+    }
+
+    /// Options shared by all kinds of messages
+    #[must_use]
+    fn common_form(&self) -> Form<'_> {
+        let mut result = Vec::new();
         if self.disable_notification {
-            result.push(("disable_notification", "true"));
+            result.push(("disable_notification", Cow::Borrowed("true")));
         }
-        if let Some(parsemode) = self.parse_mode {
-            result.push(("parse_mode", parsemode.to_str()));
+        self.message_options.append_to(&mut result);
+        result
+    }
+
+    /// Options of text messages
+    #[must_use]
+    fn base_form(&self) -> Form<'_> {
+        let mut result = Vec::new();
+        if let Some(options) = self.link_preview_options() {
+            result.push(("link_preview_options", Cow::Owned(options.to_json())));
         }
+        result.append(&mut self.common_form());
         result
     }
 
+    /// Forms to send the text with. Multiple when the text is too long for a single message.
+    #[must_use]
+    fn text_forms<'a>(&'a self, text: &'a str) -> Vec<Form<'a>> {
+        self.long_text
+            .apply(text, self.parse_mode)
+            .into_iter()
+            .map(|text| {
+                let mut form = self.base_form();
+                if let Some(parsemode) = self.parse_mode {
+                    form.push(("parse_mode", Cow::Borrowed(parsemode.to_str())));
+                }
+                form.push(("text", text));
+                form
+            })
+            .collect()
+    }
+
+    /// Forms to send the text with. Multiple when the text is too long for a single message.
+    #[must_use]
+    fn entity_text_forms<'a>(&'a self, text: &'a EntityText) -> Vec<Form<'a>> {
+        self.long_text
+            .apply_entities(text)
+            .into_iter()
+            .map(|text| {
+                let mut form = self.base_form();
+                if !text.entities.is_empty() {
+                    form.push(("entities", Cow::Owned(text.entities_json())));
+                }
+                let text = match text {
+                    Cow::Borrowed(text) => Cow::Borrowed(text.text.as_str()),
+                    Cow::Owned(text) => Cow::Owned(text.text),
+                };
+                form.push(("text", text));
+                form
+            })
+            .collect()
+    }
+
+    /// Send the forms to every target chat
+    #[cfg(feature = "ureq")]
+    fn send_forms_ureq(&self, method: &str, forms: &[Form<'_>]) -> Vec<anyhow::Result<Sent>> {
+        self.target_chats
+            .iter()
+            .map(|target_chat| {
+                self.send_to_ureq(target_chat, method, forms)
+                    .with_context(|| format!("Failed to send to Telegram chat {target_chat}"))
+            })
+            .collect()
+    }
+
+    /// Send the forms to every target chat
+    #[cfg(feature = "reqwest")]
+    async fn send_forms_reqwest(
+        &self,
+        method: &str,
+        forms: &[Form<'_>],
+    ) -> Vec<anyhow::Result<Sent>> {
+        let mut result = Vec::with_capacity(self.target_chats.len());
+        for target_chat in &self.target_chats {
+            result.push(
+                self.send_to_reqwest(target_chat, method, forms)
+                    .await
+                    .with_context(|| format!("Failed to send to Telegram chat {target_chat}")),
+            );
+        }
+        result
+    }
+
+    /// Send the forms to the target chat one after another.
+    ///
+    /// When the target group was migrated to a supergroup the request is repeated with the new chat ID.
+    /// When the flood control was exceeded the request is repeated after the given time.
+    #[cfg(feature = "ureq")]
+    fn send_to_ureq(
+        &self,
+        target_chat: &TargetChat,
+        method: &str,
+        forms: &[Form<'_>],
+    ) -> anyhow::Result<Sent> {
+        let mut sent = Sent::new(target_chat.clone());
+        let mut chat_id = target_chat.to_chat_id().into_owned();
+        for form in forms {
+            let mut retries = 0;
+            let message = loop {
+                let result = api::call_ureq(
+                    &self.api_url,
+                    self.bot_token.as_str(),
+                    method,
+                    &with_chat_id(&chat_id, form),
+                );
+                match result.as_ref().err().and_then(Retry::from_error) {
+                    Some(Retry::Migrated(new_chat_id)) if sent.migrated_to_chat_id.is_none() => {
+                        sent.migrated_to_chat_id = Some(new_chat_id);
+                        chat_id = new_chat_id.to_string();
+                    }
+                    Some(Retry::After(duration)) if retries < MAX_RETRIES => {
+                        retries += 1;
+                        std::thread::sleep(duration);
+                    }
+                    _ => break result?,
+                }
+            };
+            sent.messages.push(message);
+        }
+        Ok(sent)
+    }
+
+    /// Send the forms to the target chat one after another.
+    ///
+    /// When the target group was migrated to a supergroup the request is repeated with the new chat ID.
+    /// When the flood control was exceeded the request is repeated after the given time.
+    #[cfg(feature = "reqwest")]
+    async fn send_to_reqwest(
+        &self,
+        target_chat: &TargetChat,
+        method: &str,
+        forms: &[Form<'_>],
+    ) -> anyhow::Result<Sent> {
+        let client = api::reqwest_client()?;
+        let mut sent = Sent::new(target_chat.clone());
+        let mut chat_id = target_chat.to_chat_id().into_owned();
+        for form in forms {
+            let mut retries = 0;
+            let message = loop {
+                let result = api::call_reqwest(
+                    &client,
+                    &self.api_url,
+                    self.bot_token.as_str(),
+                    method,
+                    &with_chat_id(&chat_id, form),
+                )
+                .await;
+                match result.as_ref().err().and_then(Retry::from_error) {
+                    Some(Retry::Migrated(new_chat_id)) if sent.migrated_to_chat_id.is_none() => {
+                        sent.migrated_to_chat_id = Some(new_chat_id);
+                        chat_id = new_chat_id.to_string();
+                    }
+                    Some(Retry::After(duration)) if retries < MAX_RETRIES => {
+                        retries += 1;
+                        tokio::time::sleep(duration).await;
+                    }
+                    _ => break result?,
+                }
+            };
+            sent.messages.push(message);
+        }
+        Ok(sent)
+    }
+
+    /// Get the bot and the chats it has seen recently via [`ureq`].
+    ///
+    /// Helps to find the [`TargetChat::Id`] of a chat.
+    /// Telegram only keeps updates for 24 hours, so send a message to the bot or in the chat with the bot beforehand.
+    /// This does not work while the bot has a webhook set.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#getupdates>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the requests could not be send or not be handled by the Telegram API.
+    #[cfg(feature = "ureq")]
+    pub fn seen_chats_ureq(api_url: &Url, bot_token: &BotToken) -> anyhow::Result<SeenChats> {
+        let bot_token = bot_token.as_str();
+        let bot = api::call_ureq(api_url, bot_token, "getMe", &[])?;
+        let updates: Vec<types::Update> =
+            api::call_ureq(api_url, bot_token, "getUpdates", &seen_chats_form())?;
+        Ok(SeenChats::from_updates(bot, &updates))
+    }
+
+    /// Get the bot and the chats it has seen recently via [`reqwest`].
+    ///
+    /// Helps to find the [`TargetChat::Id`] of a chat.
+    /// Telegram only keeps updates for 24 hours, so send a message to the bot or in the chat with the bot beforehand.
+    /// This does not work while the bot has a webhook set.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#getupdates>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the requests could not be send or not be handled by the Telegram API.
+    #[cfg(feature = "reqwest")]
+    pub async fn seen_chats_reqwest(
+        api_url: &Url,
+        bot_token: &BotToken,
+    ) -> anyhow::Result<SeenChats> {
+        let bot_token = bot_token.as_str();
+        let client = api::reqwest_client()?;
+        let bot = api::call_reqwest(&client, api_url, bot_token, "getMe", &[]).await?;
+        let updates: Vec<types::Update> = api::call_reqwest(
+            &client,
+            api_url,
+            bot_token,
+            "getUpdates",
+            &seen_chats_form(),
+        )
+        .await?;
+        Ok(SeenChats::from_updates(bot, &updates))
+    }
+
+    /// Check the bot token and whether the bot can send messages to the target chats via [`ureq`].
+    ///
+    /// Uses `getMe`, `getChat` and `getChatMember` without sending any message.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the bot token is invalid or the Telegram API could not be reached.
+    /// Problems with single chats are reported in [`Verification::chats`].
+    #[cfg(feature = "ureq")]
+    pub fn verify_ureq(&self) -> anyhow::Result<Verification> {
+        let bot_token = self.bot_token.as_str();
+        let bot: User = api::call_ureq(&self.api_url, bot_token, "getMe", &[])?;
+        let bot_id = bot.id.to_string();
+        let chats = self
+            .target_chats
+            .iter()
+            .map(|target_chat| {
+                self.verify_chat_ureq(target_chat, &bot_id)
+                    .context("Failed to verify Telegram chat")
+            })
+            .collect();
+        Ok(Verification { bot, chats })
+    }
+
+    /// Check one target chat with `getChat` and `getChatMember` via [`ureq`].
+    #[cfg(feature = "ureq")]
+    fn verify_chat_ureq(
+        &self,
+        target_chat: &TargetChat,
+        bot_id: &str,
+    ) -> anyhow::Result<ChatAccess> {
+        let bot_token = self.bot_token.as_str();
+        let chat_id = target_chat.to_chat_id();
+        let info = api::call_ureq(
+            &self.api_url,
+            bot_token,
+            "getChat",
+            &[("chat_id", chat_id.clone())],
+        )?;
+        let member = api::call_ureq(
+            &self.api_url,
+            bot_token,
+            "getChatMember",
+            &[("chat_id", chat_id), ("user_id", Cow::Borrowed(bot_id))],
+        )?;
+        Ok(ChatAccess::new(target_chat.clone(), info, &member))
+    }
+
+    /// Check the bot token and whether the bot can send messages to the target chats via [`reqwest`].
+    ///
+    /// Uses `getMe`, `getChat` and `getChatMember` without sending any message.
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the bot token is invalid or the Telegram API could not be reached.
+    /// Problems with single chats are reported in [`Verification::chats`].
+    #[cfg(feature = "reqwest")]
+    pub async fn verify_reqwest(&self) -> anyhow::Result<Verification> {
+        let client = api::reqwest_client()?;
+        let bot_token = self.bot_token.as_str();
+        let bot: User = api::call_reqwest(&client, &self.api_url, bot_token, "getMe", &[]).await?;
+        let bot_id = bot.id.to_string();
+        let mut chats = Vec::with_capacity(self.target_chats.len());
+        for target_chat in &self.target_chats {
+            chats.push(
+                self.verify_chat_reqwest(&client, target_chat, &bot_id)
+                    .await
+                    .context("Failed to verify Telegram chat"),
+            );
+        }
+        Ok(Verification { bot, chats })
+    }
+
+    /// Check one target chat with `getChat` and `getChatMember` via [`reqwest`].
+    #[cfg(feature = "reqwest")]
+    async fn verify_chat_reqwest(
+        &self,
+        client: &reqwest::Client,
+        target_chat: &TargetChat,
+        bot_id: &str,
+    ) -> anyhow::Result<ChatAccess> {
+        let bot_token = self.bot_token.as_str();
+        let chat_id = target_chat.to_chat_id();
+        let info = api::call_reqwest(
+            client,
+            &self.api_url,
+            bot_token,
+            "getChat",
+            &[("chat_id", chat_id.clone())],
+        )
+        .await?;
+        let member = api::call_reqwest(
+            client,
+            &self.api_url,
+            bot_token,
+            "getChatMember",
+            &[("chat_id", chat_id), ("user_id", Cow::Borrowed(bot_id))],
+        )
+        .await?;
+        Ok(ChatAccess::new(target_chat.clone(), info, &member))
+    }
+
     /// Send a Telegram notification via [`ureq`].
     ///
+    /// Texts longer than [`MAX_TEXT_LENGTH`] are handled as configured with [`Telegram::long_text`].
+    /// When the target group was upgraded to a supergroup, the message is sent there and [`Sent::migrated_to_chat_id`] is set.
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "ureq")]
+    pub fn send_ureq(&self, text: &str) -> Vec<anyhow::Result<Sent>> {
+        self.send_forms_ureq("sendMessage", &self.text_forms(text))
+    }
+
+    /// Send a Telegram notification via [`reqwest`].
+    ///
+    /// Texts longer than [`MAX_TEXT_LENGTH`] are handled as configured with [`Telegram::long_text`].
+    /// When the target group was upgraded to a supergroup, the message is sent there and [`Sent::migrated_to_chat_id`] is set.
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "reqwest")]
+    pub async fn send_reqwest(&self, text: &str) -> Vec<anyhow::Result<Sent>> {
+        self.send_forms_reqwest("sendMessage", &self.text_forms(text))
+            .await
+    }
+
+    /// Send a Telegram notification formatted with [`MessageEntity`]s via [`ureq`].
+    ///
+    /// [`Telegram::parse_mode`] is ignored as the entities define the formatting.
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "ureq")]
+    pub fn send_entities_ureq(&self, text: &EntityText) -> Vec<anyhow::Result<Sent>> {
+        self.send_forms_ureq("sendMessage", &self.entity_text_forms(text))
+    }
+
+    /// Send a Telegram notification formatted with [`MessageEntity`]s via [`reqwest`].
+    ///
+    /// [`Telegram::parse_mode`] is ignored as the entities define the formatting.
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "reqwest")]
+    pub async fn send_entities_reqwest(&self, text: &EntityText) -> Vec<anyhow::Result<Sent>> {
+        self.send_forms_reqwest("sendMessage", &self.entity_text_forms(text))
+            .await
+    }
+
+    /// Send a location shown as a pin on a map via [`ureq`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "ureq")]
+    pub fn send_location_ureq(&self, location: &Location) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        location.append_to(&mut form);
+        self.send_forms_ureq("sendLocation", &[form])
+    }
+
+    /// Send a location shown as a pin on a map via [`reqwest`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "reqwest")]
+    pub async fn send_location_reqwest(&self, location: &Location) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        location.append_to(&mut form);
+        self.send_forms_reqwest("sendLocation", &[form]).await
+    }
+
+    /// Send a venue shown as a pin with title and address on a map via [`ureq`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "ureq")]
+    pub fn send_venue_ureq(&self, venue: &Venue) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        venue.append_to(&mut form);
+        self.send_forms_ureq("sendVenue", &[form])
+    }
+
+    /// Send a venue shown as a pin with title and address on a map via [`reqwest`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "reqwest")]
+    pub async fn send_venue_reqwest(&self, venue: &Venue) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        venue.append_to(&mut form);
+        self.send_forms_reqwest("sendVenue", &[form]).await
+    }
+
+    /// Send a poll to vote on via [`ureq`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "ureq")]
+    pub fn send_poll_ureq(&self, poll: &Poll) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        poll.append_to(&mut form);
+        self.send_forms_ureq("sendPoll", &[form])
+    }
+
+    /// Send a poll to vote on via [`reqwest`].
+    ///
+    /// Returns one result per [`Telegram::target_chats`] in the same order.
+    /// A result is an error when the request could not be send or the not be handled by the Telegram API.
+    #[must_use]
+    #[cfg(feature = "reqwest")]
+    pub async fn send_poll_reqwest(&self, poll: &Poll) -> Vec<anyhow::Result<Sent>> {
+        let mut form = self.common_form();
+        poll.append_to(&mut form);
+        self.send_forms_reqwest("sendPoll", &[form]).await
+    }
+
+    /// Form to pin or unpin the message
+    #[must_use]
+    fn pin_form(&self, message: &Message, pin: bool) -> Form<'_> {
+        let mut result = vec![
+            ("chat_id", Cow::Owned(message.chat.id.to_string())),
+            ("message_id", Cow::Owned(message.message_id.to_string())),
+        ];
+        if let Some(id) = &self.message_options.business_connection_id {
+            result.push(("business_connection_id", Cow::Borrowed(id.as_str())));
+        }
+        if pin && self.disable_notification {
+            result.push(("disable_notification", Cow::Borrowed("true")));
+        }
+        result
+    }
+
+    /// Pin a message from [`Sent::messages`] in its chat via [`ureq`].
+    ///
+    /// With [`Telegram::disable_notification`] the chat members are not notified about the pin.
+    /// The bot needs the right to pin messages in groups and channels.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#pinchatmessage>
+    ///
     /// # Errors
     ///
     /// This method errors when the request could not be send or the not be handled by the Telegram API.
-    #[allow(clippy::result_large_err)]
     #[cfg(feature = "ureq")]
-    pub fn send_ureq(&self, text: &str) -> Result<(), ureq::Error> {
-        let mut form = self.base_form();
-        let chat_id = self.target_chat.to_chat_id();
-        form.push(("chat_id", &chat_id));
-        form.push(("text", text));
-
-        ureq::post(&generate_url(&self.bot_token))
-            .header(ureq::http::header::USER_AGENT, crate::USER_AGENT_UREQ)
-            .send_form(form)?;
+    pub fn pin_message_ureq(&self, message: &Message) -> anyhow::Result<()> {
+        let form = self.pin_form(message, true);
+        api::call_ureq::<bool>(
+            &self.api_url,
+            self.bot_token.as_str(),
+            "pinChatMessage",
+            &form,
+        )?;
         Ok(())
     }
 
-    /// Send a Telegram notification via [`reqwest`].
+    /// Pin a message from [`Sent::messages`] in its chat via [`reqwest`].
+    ///
+    /// With [`Telegram::disable_notification`] the chat members are not notified about the pin.
+    /// The bot needs the right to pin messages in groups and channels.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#pinchatmessage>
     ///
     /// # Errors
     ///
     /// This method errors when the request could not be send or the not be handled by the Telegram API.
     #[cfg(feature = "reqwest")]
-    pub async fn send_reqwest(&self, text: &str) -> reqwest::Result<()> {
-        let mut form = self.base_form();
-        let chat_id = self.target_chat.to_chat_id();
-        form.push(("chat_id", &chat_id));
-        form.push(("text", text));
-
-        reqwest::ClientBuilder::new()
-            .user_agent(crate::USER_AGENT_REQWEST)
-            .build()?
-            .post(generate_url(&self.bot_token))
-            .form(&form)
-            .send()
-            .await
-            .and_then(reqwest::Response::error_for_status)
-            .map_err(reqwest::Error::without_url)?;
+    pub async fn pin_message_reqwest(&self, message: &Message) -> anyhow::Result<()> {
+        let client = api::reqwest_client()?;
+        let form = self.pin_form(message, true);
+        api::call_reqwest::<bool>(
+            &client,
+            &self.api_url,
+            self.bot_token.as_str(),
+            "pinChatMessage",
+            &form,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Unpin a message from [`Sent::messages`] in its chat via [`ureq`].
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#unpinchatmessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Telegram API.
+    #[cfg(feature = "ureq")]
+    pub fn unpin_message_ureq(&self, message: &Message) -> anyhow::Result<()> {
+        let form = self.pin_form(message, false);
+        api::call_ureq::<bool>(
+            &self.api_url,
+            self.bot_token.as_str(),
+            "unpinChatMessage",
+            &form,
+        )?;
+        Ok(())
+    }
+
+    /// Unpin a message from [`Sent::messages`] in its chat via [`reqwest`].
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#unpinchatmessage>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Telegram API.
+    #[cfg(feature = "reqwest")]
+    pub async fn unpin_message_reqwest(&self, message: &Message) -> anyhow::Result<()> {
+        let client = api::reqwest_client()?;
+        let form = self.pin_form(message, false);
+        api::call_reqwest::<bool>(
+            &client,
+            &self.api_url,
+            self.bot_token.as_str(),
+            "unpinChatMessage",
+            &form,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Forms to reply with the text to the message
+    ///
+    /// The reply goes to the topic of the message instead of the one configured in [`Telegram::message_options`].
+    /// It is sent by the bot itself, not on behalf of a business connection.
+    #[must_use]
+    fn reply_forms<'a>(&'a self, message: &'a Message, text: &'a str) -> Vec<Form<'a>> {
+        let reply_parameters = format!(r#"{{"message_id":{}}}"#, message.message_id);
+        let mut forms = self.text_forms(text);
+        for form in &mut forms {
+            form.retain(|(key, _)| *key != "message_thread_id" && *key != "business_connection_id");
+            if let Some(id) = message.message_thread_id {
+                form.push(("message_thread_id", Cow::Owned(id.to_string())));
+            }
+            form.push(("reply_parameters", Cow::Owned(reply_parameters.clone())));
+        }
+        forms
+    }
+
+    /// Reply with a text to a message received via [`Telegram::run_updates_ureq`] using [`ureq`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Telegram API.
+    #[cfg(feature = "ureq")]
+    pub fn reply_ureq(&self, message: &Message, text: &str) -> anyhow::Result<Sent> {
+        let target_chat = TargetChat::Id(message.chat.id);
+        self.send_to_ureq(
+            &target_chat,
+            "sendMessage",
+            &self.reply_forms(message, text),
+        )
+    }
+
+    /// Reply with a text to a message received via [`Telegram::run_updates_reqwest`] using [`reqwest`].
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the request could not be send or the not be handled by the Telegram API.
+    #[cfg(feature = "reqwest")]
+    pub async fn reply_reqwest(&self, message: &Message, text: &str) -> anyhow::Result<Sent> {
+        let target_chat = TargetChat::Id(message.chat.id);
+        self.send_to_reqwest(
+            &target_chat,
+            "sendMessage",
+            &self.reply_forms(message, text),
+        )
+        .await
+    }
+
+    /// Pass the messages of the updates to the handler and remember the offset of the next update.
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
+    fn handle_updates<F>(
+        &self,
+        updates: Vec<types::Update>,
+        bot_username: Option<&str>,
+        offset: &mut Option<i64>,
+        handler: &mut F,
+    ) -> ControlFlow<()>
+    where
+        F: FnMut(&Incoming) -> ControlFlow<()>,
+    {
+        for update in updates {
+            *offset = Some(update.update_id + 1);
+            if let Some(incoming) = Incoming::from_update(update, &self.target_chats, bot_username)
+            {
+                handler(&incoming)?;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Receive messages sent to the bot via long polling with [`ureq`] and pass them to the handler.
+    ///
+    /// Runs until the handler returns [`ControlFlow::Break`].
+    /// Handled updates are confirmed so they are not received again on the next run.
+    /// Everyone can write to a bot, so check [`Incoming::from_target_chat`] before reacting.
+    /// This does not work while the bot has a webhook set.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#getupdates>
+    ///
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    ///
+    /// # fn example(telegram: &pling::Telegram) -> anyhow::Result<()> {
+    /// telegram.run_updates_ureq(|incoming| match incoming.command.as_ref() {
+    ///     Some(command) if incoming.from_target_chat && command.name == "ack" => {
+    ///         ControlFlow::Break(())
+    ///     }
+    ///     _ => ControlFlow::Continue(()),
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the bot token is invalid or the Telegram API rejects `getUpdates`, like while a webhook is set.
+    /// Failed connections, server errors and the flood control are waited out and polling continues.
+    #[cfg(feature = "ureq")]
+    pub fn run_updates_ureq<F>(&self, mut handler: F) -> anyhow::Result<()>
+    where
+        F: FnMut(&Incoming) -> ControlFlow<()>,
+    {
+        let bot_token = self.bot_token.as_str();
+        let bot: User = api::call_ureq(&self.api_url, bot_token, "getMe", &[])?;
+        let mut offset = None;
+        loop {
+            let result = api::call_ureq(
+                &self.api_url,
+                bot_token,
+                "getUpdates",
+                &updates_form(offset, LONG_POLLING_TIMEOUT),
+            );
+            let updates = match result {
+                Ok(updates) => updates,
+                Err(error) => {
+                    std::thread::sleep(polling_retry_delay(&error).ok_or(error)?);
+                    continue;
+                }
+            };
+            let flow =
+                self.handle_updates(updates, bot.username.as_deref(), &mut offset, &mut handler);
+            if flow.is_break() {
+                break;
+            }
+        }
+        api::call_ureq::<Vec<types::Update>>(
+            &self.api_url,
+            bot_token,
+            "getUpdates",
+            &confirm_updates_form(offset),
+        )?;
+        Ok(())
+    }
+
+    /// Receive messages sent to the bot via long polling with [`reqwest`] and pass them to the handler.
+    ///
+    /// Runs until the handler returns [`ControlFlow::Break`].
+    /// Handled updates are confirmed so they are not received again on the next run.
+    /// Everyone can write to a bot, so check [`Incoming::from_target_chat`] before reacting.
+    /// This does not work while the bot has a webhook set.
+    ///
+    /// Documentation: <https://core.telegram.org/bots/api#getupdates>
+    ///
+    /// # Errors
+    ///
+    /// This method errors when the bot token is invalid or the Telegram API rejects `getUpdates`, like while a webhook is set.
+    /// Failed connections, server errors and the flood control are waited out and polling continues.
+    #[cfg(feature = "reqwest")]
+    pub async fn run_updates_reqwest<F>(&self, mut handler: F) -> anyhow::Result<()>
+    where
+        F: FnMut(&Incoming) -> ControlFlow<()>,
+    {
+        let client = api::reqwest_client()?;
+        let bot_token = self.bot_token.as_str();
+        let bot: User = api::call_reqwest(&client, &self.api_url, bot_token, "getMe", &[]).await?;
+        let mut offset = None;
+        loop {
+            let result = api::call_reqwest(
+                &client,
+                &self.api_url,
+                bot_token,
+                "getUpdates",
+                &updates_form(offset, LONG_POLLING_TIMEOUT),
+            )
+            .await;
+            let updates = match result {
+                Ok(updates) => updates,
+                Err(error) => {
+                    tokio::time::sleep(polling_retry_delay(&error).ok_or(error)?).await;
+                    continue;
+                }
+            };
+            let flow =
+                self.handle_updates(updates, bot.username.as_deref(), &mut offset, &mut handler);
+            if flow.is_break() {
+                break;
+            }
+        }
+        api::call_reqwest::<Vec<types::Update>>(
+            &client,
+            &self.api_url,
+            bot_token,
+            "getUpdates",
+            &confirm_updates_form(offset),
+        )
+        .await?;
         Ok(())
     }
 }
 
+/// Form to wait for new messages after the given offset
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
 #[must_use]
-fn generate_url(bot_token: &str) -> String {
-    format!("https://api.telegram.org/bot{bot_token}/sendMessage")
+fn updates_form(offset: Option<i64>, timeout: &'static str) -> Form<'static> {
+    let mut form = vec![
+        ("timeout", Cow::Borrowed(timeout)),
+        (
+            "allowed_updates",
+            Cow::Borrowed(r#"["message","channel_post"]"#),
+        ),
+    ];
+    if let Some(offset) = offset {
+        form.push(("offset", Cow::Owned(offset.to_string())));
+    }
+    form
+}
+
+/// Form to get all updates which contain a chat.
+///
+/// Telegram keeps `allowed_updates` for later requests, so the narrower list of [`updates_form`] has to be replaced.
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[must_use]
+fn seen_chats_form() -> Form<'static> {
+    vec![(
+        "allowed_updates",
+        Cow::Borrowed(
+            r#"["message","edited_message","channel_post","edited_channel_post","my_chat_member"]"#,
+        ),
+    )]
+}
+
+/// Form to confirm the updates before the offset without waiting for new ones
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[must_use]
+fn confirm_updates_form(offset: Option<i64>) -> Form<'static> {
+    let mut form = updates_form(offset, "0");
+    form.push(("limit", Cow::Borrowed("1")));
+    form
+}
+
+/// Time to wait before polling again after `getUpdates` failed
+///
+/// Returns `None` when the Telegram API rejected the request and repeating it will not help.
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+#[must_use]
+fn polling_retry_delay(error: &anyhow::Error) -> Option<Duration> {
+    if let Some(Retry::After(duration)) = Retry::from_error(error) {
+        return Some(duration);
+    }
+    match error.downcast_ref::<ApiError>() {
+        Some(api_error) if api_error.error_code < 500 => None,
+        _ => Some(POLLING_RETRY_DELAY),
+    }
+}
+
+/// Prepend the `chat_id` to the form
+#[must_use]
+fn with_chat_id<'a>(chat_id: &'a str, form: &'a [(&'static str, Cow<'a, str>)]) -> Form<'a> {
+    let mut result = Vec::with_capacity(form.len() + 1);
+    result.push(("chat_id", Cow::Borrowed(chat_id)));
+    result.extend_from_slice(form);
+    result
+}
+
+/// Reason to repeat a failed request
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+enum Retry {
+    /// The group was upgraded to a supergroup with this chat ID
+    Migrated(i64),
+    /// Flood control was exceeded, wait before trying again
+    After(Duration),
+}
+
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+impl Retry {
+    #[must_use]
+    fn from_error(error: &anyhow::Error) -> Option<Self> {
+        let parameters = &error.downcast_ref::<ApiError>()?.parameters;
+        if let Some(chat_id) = parameters.migrate_to_chat_id {
+            return Some(Self::Migrated(chat_id));
+        }
+        let duration = Duration::from_secs(parameters.retry_after?);
+        (duration <= MAX_RETRY_AFTER).then_some(Self::After(duration))
+    }
+}
+
+fn default_api_url() -> Url {
+    Url::parse(DEFAULT_API_URL).expect("default API URL should be valid")
+}
+
+#[must_use]
+fn generate_url(api_url: &Url, bot_token: &str, method: &str) -> String {
+    let api_url = api_url.as_str().trim_end_matches('/');
+    format!("{api_url}/bot{bot_token}/{method}")
 }
 
 #[test]
 fn url_correct() {
-    let url = generate_url("123:ABC");
+    let url = generate_url(&default_api_url(), "123:ABC", "sendMessage");
     assert_eq!(url, "https://api.telegram.org/bot123:ABC/sendMessage");
 }
 
+#[test]
+fn url_self_hosted_with_path() {
+    let api_url = Url::parse("http://localhost:8081/telegram/").unwrap();
+    let url = generate_url(&api_url, "123:ABC", "sendMessage");
+    assert_eq!(url, "http://localhost:8081/telegram/bot123:ABC/sendMessage");
+}
+
 #[test]
 fn base_form_minimal() {
     let telegram = Telegram {
-        bot_token: "123:ABC".to_owned(),
-        target_chat: TargetChat::Id(1234),
+        bot_token: bot_token::TEST_TOKEN.parse().unwrap(),
+        target_chats: vec![TargetChat::Id(1234)],
+        api_url: default_api_url(),
         disable_web_page_preview: false,
+        link_preview_options: None,
         disable_notification: false,
         parse_mode: None,
+        long_text: LongText::Split,
+        message_options: MessageOptions::default(),
     };
     let form = telegram.base_form();
     dbg!(&form);
@@ -119,27 +992,596 @@ fn base_form_minimal() {
 #[test]
 fn base_form_disable_preview() {
     let telegram = Telegram {
-        bot_token: "123:ABC".to_owned(),
-        target_chat: TargetChat::Id(1234),
+        bot_token: bot_token::TEST_TOKEN.parse().unwrap(),
+        target_chats: vec![TargetChat::Id(1234)],
+        api_url: default_api_url(),
         disable_web_page_preview: true,
+        link_preview_options: None,
         disable_notification: false,
         parse_mode: None,
+        long_text: LongText::Split,
+        message_options: MessageOptions::default(),
+    };
+    let form = telegram.base_form();
+    dbg!(&form);
+    assert_eq!(
+        form,
+        [(
+            "link_preview_options",
+            Cow::Borrowed(r#"{"is_disabled":true}"#)
+        )]
+    );
+}
+
+#[test]
+fn base_form_link_preview_options() {
+    let telegram = Telegram {
+        link_preview_options: Some(LinkPreviewOptions {
+            show_above_text: true,
+            ..LinkPreviewOptions::default()
+        }),
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(1234))
     };
     let form = telegram.base_form();
     dbg!(&form);
-    assert_eq!(form, [("disable_web_page_preview", "true"),]);
+    assert_eq!(
+        form,
+        [(
+            "link_preview_options",
+            Cow::Borrowed(r#"{"show_above_text":true}"#)
+        )]
+    );
 }
 
 #[test]
-fn base_form_parse_mode() {
+fn base_form_disable_preview_keeps_link_preview_options() {
     let telegram = Telegram {
-        bot_token: "123:ABC".to_owned(),
-        target_chat: TargetChat::Id(1234),
+        disable_web_page_preview: true,
+        link_preview_options: Some(LinkPreviewOptions {
+            show_above_text: true,
+            ..LinkPreviewOptions::default()
+        }),
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(1234))
+    };
+    let form = telegram.base_form();
+    dbg!(&form);
+    assert_eq!(
+        form,
+        [(
+            "link_preview_options",
+            Cow::Borrowed(r#"{"is_disabled":true,"show_above_text":true}"#)
+        )]
+    );
+}
+
+#[test]
+fn text_forms_parse_mode() {
+    let telegram = Telegram {
+        bot_token: bot_token::TEST_TOKEN.parse().unwrap(),
+        target_chats: vec![TargetChat::Id(1234)],
+        api_url: default_api_url(),
         disable_web_page_preview: false,
+        link_preview_options: None,
         disable_notification: false,
         parse_mode: Some(ParseMode::HTML),
+        long_text: LongText::Split,
+        message_options: MessageOptions::default(),
+    };
+    let forms = telegram.text_forms("<b>Hello</b>");
+    dbg!(&forms);
+    assert_eq!(
+        forms,
+        [[
+            ("parse_mode", Cow::Borrowed("HTML")),
+            ("text", Cow::Borrowed("<b>Hello</b>")),
+        ]]
+    );
+}
+
+#[test]
+fn entity_text_forms_ignore_parse_mode() {
+    let telegram = Telegram {
+        parse_mode: Some(ParseMode::HTML),
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(1234))
+    };
+    let text = EntityText::new().bold("<Hello>");
+    let forms = telegram.entity_text_forms(&text);
+    dbg!(&forms);
+    assert_eq!(
+        forms,
+        [[
+            (
+                "entities",
+                Cow::Borrowed(r#"[{"type":"bold","offset":0,"length":7}]"#)
+            ),
+            ("text", Cow::Borrowed("<Hello>")),
+        ]]
+    );
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn seen_chats_from_fake_api() {
+    let api = fake_api::FakeApi::start(|request| {
+        match request.method.as_str() {
+        "getMe" => r#"{"ok":true,"result":{"id":1,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        "getUpdates" => r#"{"ok":true,"result":[{"update_id":1,"message":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"hi"}}]}"#.to_owned(),
+        _ => r#"{"ok":false,"error_code":404,"description":"Not Found"}"#.to_owned(),
+    }
+    });
+    let seen =
+        Telegram::seen_chats_ureq(&api.url, &bot_token::TEST_TOKEN.parse().unwrap()).unwrap();
+    assert_eq!(seen.bot.username.as_deref(), Some("pling_bot"));
+    assert_eq!(seen.chats.len(), 1);
+    assert_eq!(seen.chats[0].id, -100_123);
+    let methods = api
+        .requests()
+        .into_iter()
+        .map(|request| request.method)
+        .collect::<Vec<_>>();
+    assert_eq!(methods, ["getMe", "getUpdates"]);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn seen_chats_invalid_token() {
+    let api = fake_api::FakeApi::start(|_| {
+        r#"{"ok":false,"error_code":401,"description":"Unauthorized"}"#.to_owned()
+    });
+    let error =
+        Telegram::seen_chats_ureq(&api.url, &bot_token::TEST_TOKEN.parse().unwrap()).unwrap_err();
+    assert_eq!(error.to_string(), "Telegram API error 401: Unauthorized");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn send_follows_supergroup_migration() {
+    let api = fake_api::FakeApi::start(|request| {
+        if request.get("chat_id") == Some("-123") {
+            r#"{"ok":false,"error_code":400,"description":"Bad Request: group chat was upgraded to a supergroup chat","parameters":{"migrate_to_chat_id":-100123}}"#.to_owned()
+        } else {
+            r#"{"ok":true,"result":{"message_id":5,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"ok"}}"#.to_owned()
+        }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(-123))
+    };
+    let text = "line\n".repeat(1000);
+    let mut results = telegram.send_ureq(&text);
+    let sent = results.remove(0).unwrap();
+    assert_eq!(sent.migrated_to_chat_id, Some(-100_123));
+    assert_eq!(sent.messages.len(), 2);
+    assert_eq!(sent.messages[0].chat.id, -100_123);
+
+    let chat_ids = api
+        .requests()
+        .iter()
+        .map(|request| request.get("chat_id").unwrap().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(chat_ids, ["-123", "-100123", "-100123"]);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn send_returns_api_error() {
+    let api = fake_api::FakeApi::start(|_| {
+        r#"{"ok":false,"error_code":400,"description":"Bad Request: chat not found"}"#.to_owned()
+    });
+    let telegram = Telegram {
+        api_url: api.url,
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(-123))
+    };
+    let error = telegram.send_ureq("Hello").remove(0).unwrap_err();
+    assert_eq!(error.to_string(), "Failed to send to Telegram chat -123");
+    let error = error.downcast_ref::<ApiError>().unwrap();
+    assert_eq!(error.description, "Bad Request: chat not found");
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn send_to_multiple_chats() {
+    let api = fake_api::FakeApi::start(|request| match request.get("chat_id") {
+        Some("@blocked") => {
+            r#"{"ok":false,"error_code":403,"description":"Forbidden: bot was kicked"}"#.to_owned()
+        }
+        Some(chat_id) => format!(
+            r#"{{"ok":true,"result":{{"message_id":1,"date":0,"chat":{{"id":{},"type":"private"}}}}}}"#,
+            chat_id.parse::<i64>().unwrap_or(42)
+        ),
+        None => unreachable!("chat_id is always set"),
+    });
+    let telegram = Telegram {
+        api_url: api.url,
+        target_chats: vec![
+            TargetChat::Id(1),
+            TargetChat::Username("@blocked".to_owned()),
+            TargetChat::Id(3),
+        ],
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(0))
+    };
+    let results = telegram.send_ureq("Hello");
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().messages[0].chat.id, 1);
+    assert_eq!(
+        results[1].as_ref().unwrap_err().to_string(),
+        "Failed to send to Telegram chat @blocked"
+    );
+    let sent = results[2].as_ref().unwrap();
+    assert_eq!(sent.target_chat, TargetChat::Id(3));
+    assert_eq!(sent.messages[0].chat.id, 3);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn send_waits_for_flood_control() {
+    let flooded = std::sync::atomic::AtomicBool::new(false);
+    let api = fake_api::FakeApi::start(move |_| {
+        if flooded.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            r#"{"ok":true,"result":{"message_id":2,"date":0,"chat":{"id":1,"type":"private"}}}"#
+                .to_owned()
+        } else {
+            r#"{"ok":false,"error_code":429,"description":"Too Many Requests: retry after 0","parameters":{"retry_after":0}}"#.to_owned()
+        }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(1))
+    };
+    let sent = telegram.send_ureq("Hello").remove(0).unwrap();
+    assert_eq!(sent.messages[0].message_id, 2);
+    assert_eq!(api.requests().len(), 2);
+}
+
+#[test]
+fn base_form_protect_content_in_topic() {
+    let telegram = Telegram {
+        disable_notification: true,
+        message_options: MessageOptions {
+            message_thread_id: Some(7),
+            protect_content: true,
+            ..MessageOptions::default()
+        },
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(1234))
     };
     let form = telegram.base_form();
-    dbg!(&form);
-    assert_eq!(form, [("parse_mode", "HTML"),]);
+    assert_eq!(
+        form,
+        [
+            ("disable_notification", Cow::Borrowed("true")),
+            ("message_thread_id", Cow::Borrowed("7")),
+            ("protect_content", Cow::Borrowed("true")),
+        ]
+    );
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn send_poll_into_topic() {
+    let api = fake_api::FakeApi::start(|_| {
+        r#"{"ok":true,"result":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"}}}"#.to_owned()
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        disable_notification: true,
+        message_options: MessageOptions {
+            message_thread_id: Some(7),
+            ..MessageOptions::default()
+        },
+        parse_mode: Some(ParseMode::HTML),
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    let poll = Poll::new(
+        "Severity?".to_owned(),
+        vec!["SEV1".to_owned(), "SEV2".to_owned()],
+    );
+    let sent = telegram.send_poll_ureq(&poll).remove(0).unwrap();
+    assert_eq!(sent.messages[0].message_id, 1);
+
+    let requests = api.requests();
+    assert_eq!(requests[0].method, "sendPoll");
+    assert_eq!(requests[0].get("chat_id"), Some("-100123"));
+    assert_eq!(requests[0].get("disable_notification"), Some("true"));
+    assert_eq!(requests[0].get("message_thread_id"), Some("7"));
+    assert_eq!(requests[0].get("question"), Some("Severity?"));
+    assert_eq!(requests[0].get("parse_mode"), None);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn pin_and_unpin_sent_message() {
+    let api = fake_api::FakeApi::start(|request| {
+        if request.method == "sendMessage" {
+            r#"{"ok":true,"result":{"message_id":5,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"}}}"#.to_owned()
+        } else {
+            r#"{"ok":true,"result":true}"#.to_owned()
+        }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        disable_notification: true,
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    let sent = telegram.send_ureq("Incident ongoing").remove(0).unwrap();
+    telegram.pin_message_ureq(&sent.messages[0]).unwrap();
+    telegram.unpin_message_ureq(&sent.messages[0]).unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests[1].method, "pinChatMessage");
+    assert_eq!(requests[1].get("chat_id"), Some("-100123"));
+    assert_eq!(requests[1].get("message_id"), Some("5"));
+    assert_eq!(requests[1].get("disable_notification"), Some("true"));
+    assert_eq!(requests[2].method, "unpinChatMessage");
+    assert_eq!(requests[2].get("message_id"), Some("5"));
+    assert_eq!(requests[2].get("disable_notification"), None);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn verify_chats() {
+    let api = fake_api::FakeApi::start(|request| {
+        match (request.method.as_str(), request.get("chat_id")) {
+        ("getMe", _) => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        ("getChat", Some("-100123")) => r#"{"ok":true,"result":{"id":-100123,"type":"supergroup","title":"Ops"}}"#.to_owned(),
+        ("getChatMember", Some("-100123")) => {
+            assert_eq!(request.get("user_id"), Some("99"));
+            r#"{"ok":true,"result":{"status":"member","user":{"id":99}}}"#.to_owned()
+        }
+        _ => r#"{"ok":false,"error_code":400,"description":"Bad Request: chat not found"}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        target_chats: vec![
+            TargetChat::Id(-100_123),
+            TargetChat::Username("@missing".to_owned()),
+        ],
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(0))
+    };
+    let verification = telegram.verify_ureq().unwrap();
+    assert_eq!(verification.bot.username.as_deref(), Some("pling_bot"));
+    let ops = verification.chats[0].as_ref().unwrap();
+    assert_eq!(ops.chat.title.as_deref(), Some("Ops"));
+    assert!(ops.can_send_messages);
+    let error = verification.chats[1].as_ref().unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "Failed to verify Telegram chat: Telegram API error 400: Bad Request: chat not found"
+    );
+    assert!(!verification.can_send_everywhere());
+    assert!(api
+        .requests()
+        .iter()
+        .all(|request| !request.method.starts_with("send")));
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn verify_skips_member_of_failed_chat() {
+    let api = fake_api::FakeApi::start(|request| {
+        match request.method.as_str() {
+        "getMe" => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        "getChat" => r#"{"ok":false,"error_code":403,"description":"Forbidden: bot was kicked from the supergroup chat"}"#.to_owned(),
+        _ => r#"{"ok":true,"result":{"status":"member","user":{"id":99}}}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        target_chats: vec![TargetChat::Id(-100_123), TargetChat::Id(-100_456)],
+        ..Telegram::new(bot_token::TEST_TOKEN.parse().unwrap(), TargetChat::Id(0))
+    };
+    let verification = telegram.verify_ureq().unwrap();
+    for chat in &verification.chats {
+        assert_eq!(
+            format!("{:#}", chat.as_ref().unwrap_err()),
+            "Failed to verify Telegram chat: Telegram API error 403: Forbidden: bot was kicked from the supergroup chat"
+        );
+    }
+    let methods = api
+        .requests()
+        .into_iter()
+        .map(|request| request.method)
+        .collect::<Vec<_>>();
+    assert_eq!(methods, ["getMe", "getChat", "getChat"]);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn run_updates_until_ack() {
+    let api = fake_api::FakeApi::start(|request| {
+        match (request.method.as_str(), request.get("offset")) {
+        ("getMe", _) => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        ("getUpdates", None) => r#"{"ok":true,"result":[
+            {"update_id":10,"message":{"message_id":1,"date":0,"chat":{"id":42,"type":"private","first_name":"Stranger"},"text":"/ack"}},
+            {"update_id":11,"message":{"message_id":2,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/silence@pling_bot 1h"}}
+        ]}"#.to_owned(),
+        ("getUpdates", Some("12")) => r#"{"ok":true,"result":[
+            {"update_id":12,"message":{"message_id":3,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/ack"}},
+            {"update_id":13,"message":{"message_id":4,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/ack"}}
+        ]}"#.to_owned(),
+        _ => r#"{"ok":true,"result":[]}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    let mut commands = Vec::new();
+    telegram
+        .run_updates_ureq(|incoming| {
+            let Some(command) = &incoming.command else {
+                return ControlFlow::Continue(());
+            };
+            if !incoming.from_target_chat {
+                return ControlFlow::Continue(());
+            }
+            commands.push(command.clone());
+            if command.name == "ack" {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+    assert_eq!(
+        commands,
+        [
+            Command {
+                name: "silence".to_owned(),
+                args: "1h".to_owned()
+            },
+            Command {
+                name: "ack".to_owned(),
+                args: String::new()
+            },
+        ]
+    );
+
+    let requests = api.requests();
+    let confirm = requests.last().unwrap();
+    assert_eq!(confirm.method, "getUpdates");
+    assert_eq!(confirm.get("offset"), Some("13"));
+    assert_eq!(confirm.get("timeout"), Some("0"));
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn run_updates_waits_for_flood_control() {
+    let limited = std::sync::atomic::AtomicBool::new(true);
+    let api = fake_api::FakeApi::start(move |request| {
+        match request.method.as_str() {
+        "getMe" => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        "getUpdates" if limited.swap(false, std::sync::atomic::Ordering::Relaxed) => r#"{"ok":false,"error_code":429,"description":"Too Many Requests: retry after 0","parameters":{"retry_after":0}}"#.to_owned(),
+        _ => r#"{"ok":true,"result":[{"update_id":10,"message":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/ack"}}]}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    telegram
+        .run_updates_ureq(|_| ControlFlow::Break(()))
+        .unwrap();
+    let methods = api
+        .requests()
+        .into_iter()
+        .map(|request| request.method)
+        .collect::<Vec<_>>();
+    assert_eq!(methods, ["getMe", "getUpdates", "getUpdates", "getUpdates"]);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn run_updates_stops_on_conflict() {
+    let api = fake_api::FakeApi::start(|request| {
+        match request.method.as_str() {
+        "getMe" => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        _ => r#"{"ok":false,"error_code":409,"description":"Conflict: can't use getUpdates method while webhook is active"}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    let error = telegram
+        .run_updates_ureq(|_| ControlFlow::Break(()))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Telegram API error 409: Conflict: can't use getUpdates method while webhook is active"
+    );
+    assert_eq!(api.requests().len(), 2);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn seen_chats_after_run_updates() {
+    let api = fake_api::FakeApi::start(|request| {
+        match request.method.as_str() {
+        "getMe" => r#"{"ok":true,"result":{"id":99,"is_bot":true,"first_name":"pling","username":"pling_bot"}}"#.to_owned(),
+        "getUpdates" => r#"{"ok":true,"result":[{"update_id":10,"message":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/ack"}}]}"#.to_owned(),
+        _ => r#"{"ok":false,"error_code":404,"description":"Not Found"}"#.to_owned(),
+    }
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    telegram
+        .run_updates_ureq(|_| ControlFlow::Break(()))
+        .unwrap();
+    Telegram::seen_chats_ureq(&api.url, &telegram.bot_token).unwrap();
+
+    let requests = api.requests();
+    let seen_chats = requests.last().unwrap();
+    assert_eq!(seen_chats.method, "getUpdates");
+    assert_eq!(
+        seen_chats.get("allowed_updates"),
+        Some(
+            r#"["message","edited_message","channel_post","edited_channel_post","my_chat_member"]"#
+        )
+    );
+    assert_ne!(
+        requests[1].get("allowed_updates"),
+        seen_chats.get("allowed_updates")
+    );
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn reply_to_message() {
+    let api = fake_api::FakeApi::start(|_| {
+        r#"{"ok":true,"result":{"message_id":8,"date":0,"chat":{"id":42,"type":"private","first_name":"Edgar"}}}"#.to_owned()
+    });
+    let telegram = Telegram {
+        api_url: api.url.clone(),
+        message_options: MessageOptions {
+            business_connection_id: Some("business".to_owned()),
+            message_thread_id: Some(5),
+            ..MessageOptions::default()
+        },
+        ..Telegram::new(
+            bot_token::TEST_TOKEN.parse().unwrap(),
+            TargetChat::Id(-100_123),
+        )
+    };
+    let message = serde_json::from_str::<Message>(
+        r#"{"message_id":7,"date":0,"chat":{"id":42,"type":"private","first_name":"Edgar"},"text":"/ack"}"#,
+    )
+    .unwrap();
+    telegram.reply_ureq(&message, "Acknowledged").unwrap();
+    let in_topic = serde_json::from_str::<Message>(
+        r#"{"message_id":9,"message_thread_id":3,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops","is_forum":true},"text":"/ack"}"#,
+    )
+    .unwrap();
+    telegram.reply_ureq(&in_topic, "Acknowledged").unwrap();
+
+    let requests = api.requests();
+    assert_eq!(requests[0].get("chat_id"), Some("42"));
+    assert_eq!(
+        requests[0].get("reply_parameters"),
+        Some(r#"{"message_id":7}"#)
+    );
+    assert_eq!(requests[0].get("message_thread_id"), None);
+    assert_eq!(requests[0].get("business_connection_id"), None);
+    assert_eq!(requests[1].get("message_thread_id"), Some("3"));
+    assert_eq!(requests[1].get("business_connection_id"), None);
 }
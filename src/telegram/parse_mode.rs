@@ -1,9 +1,12 @@
+use super::escape;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum ParseMode {
     #[allow(clippy::upper_case_acronyms)]
     HTML,
     #[deprecated = "Use MarkdownV2 instead"]
+    #[cfg_attr(feature = "clap", value(hide = true))]
     Markdown,
     MarkdownV2,
 }
@@ -31,6 +34,28 @@ impl ParseMode {
             Self::MarkdownV2 => "MarkdownV2",
         }
     }
+
+    /// Escape the text so that it is shown as is instead of being interpreted as formatting.
+    ///
+    /// The result is meant for plain text outside of formatting.
+    /// Escaping differs inside of code, links and, for the deprecated [`ParseMode::Markdown`], all entities,
+    /// so use [`TextBuilder`](super::TextBuilder) to add formatted text.
+    ///
+    /// ```
+    /// use pling::TelegramParseMode;
+    ///
+    /// assert_eq!(TelegramParseMode::HTML.escape("1 < 2"), "1 &lt; 2");
+    /// assert_eq!(TelegramParseMode::MarkdownV2.escape("v1.2"), "v1\\.2");
+    /// ```
+    #[must_use]
+    pub fn escape(self, text: &str) -> String {
+        match self {
+            Self::HTML => escape::html(text),
+            #[allow(deprecated)]
+            Self::Markdown => escape::markdown(text),
+            Self::MarkdownV2 => escape::markdown_v2(text),
+        }
+    }
 }
 
 impl std::fmt::Display for ParseMode {
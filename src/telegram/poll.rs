@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use super::Form;
+
+/// Poll to vote on
+///
+/// Documentation: <https://core.telegram.org/bots/api#sendpoll>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Poll {
+    /// Poll question, 1-300 characters
+    pub question: String,
+
+    /// Answer options, 2-12 options with 1-100 characters each
+    pub options: Vec<String>,
+
+    /// Show who voted for what. Polls are anonymous by default.
+    pub is_public: bool,
+
+    pub allows_multiple_answers: bool,
+}
+
+#[derive(serde::Serialize)]
+struct InputPollOption<'a> {
+    text: &'a str,
+}
+
+impl Poll {
+    #[must_use]
+    pub const fn new(question: String, options: Vec<String>) -> Self {
+        Self {
+            question,
+            options,
+            is_public: false,
+            allows_multiple_answers: false,
+        }
+    }
+
+    #[must_use]
+    fn options_json(&self) -> String {
+        let options = self
+            .options
+            .iter()
+            .map(|text| InputPollOption { text })
+            .collect::<Vec<_>>();
+        serde_json::to_string(&options).expect("InputPollOption should always be serializable")
+    }
+
+    pub(crate) fn append_to<'a>(&'a self, form: &mut Form<'a>) {
+        form.push(("question", Cow::Borrowed(&self.question)));
+        form.push(("options", Cow::Owned(self.options_json())));
+        if self.is_public {
+            form.push(("is_anonymous", Cow::Borrowed("false")));
+        }
+        if self.allows_multiple_answers {
+            form.push(("allows_multiple_answers", Cow::Borrowed("true")));
+        }
+    }
+}
+
+#[test]
+fn poll_form() {
+    let poll = Poll {
+        allows_multiple_answers: true,
+        ..Poll::new(
+            "Severity?".to_owned(),
+            vec!["SEV1".to_owned(), "SEV2 \"minor\"".to_owned()],
+        )
+    };
+    let mut form = Vec::new();
+    poll.append_to(&mut form);
+    assert_eq!(
+        form,
+        [
+            ("question", Cow::Borrowed("Severity?")),
+            (
+                "options",
+                Cow::Borrowed(r#"[{"text":"SEV1"},{"text":"SEV2 \"minor\""}]"#)
+            ),
+            ("allows_multiple_answers", Cow::Borrowed("true")),
+        ]
+    );
+}
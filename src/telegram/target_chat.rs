@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use super::types::Chat;
+
 /// Unique identifier for the target chat or username of the target channel (in the format `@channelusername`)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TargetChat {
@@ -21,13 +23,34 @@ impl core::str::FromStr for TargetChat {
             return Ok(Self::Id(id));
         }
 
-        if !str.starts_with('@') {
-            return Err("Telegram username needs to start with an @ symbol");
+        let username = str
+            .strip_prefix('@')
+            .ok_or("Telegram username needs to start with an @ symbol")?;
+        if !(5..=32).contains(&username.len()) {
+            return Err("Telegram username needs to be 5 to 32 characters long");
+        }
+        if !username
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        {
+            return Err("Telegram username can only contain a-z, 0-9 and underscores");
+        }
+        if !username.starts_with(|char: char| char.is_ascii_alphabetic()) {
+            return Err("Telegram username needs to start with a letter");
         }
         Ok(Self::Username(str.to_owned()))
     }
 }
 
+impl core::fmt::Display for TargetChat {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Id(id) => id.fmt(fmt),
+            Self::Username(username) => username.fmt(fmt),
+        }
+    }
+}
+
 impl TargetChat {
     #[must_use]
     pub fn to_chat_id(&self) -> Cow<'_, str> {
@@ -36,6 +59,19 @@ impl TargetChat {
             Self::Username(username) => Cow::Borrowed(username),
         }
     }
+
+    /// The chat is the one meant by this target chat
+    #[must_use]
+    pub fn is_chat(&self, chat: &Chat) -> bool {
+        match self {
+            Self::Id(id) => chat.id == *id,
+            Self::Username(username) => chat.username.as_deref().is_some_and(|chat_username| {
+                username
+                    .trim_start_matches('@')
+                    .eq_ignore_ascii_case(chat_username)
+            }),
+        }
+    }
 }
 
 #[test]
@@ -56,3 +92,33 @@ fn username_no_at() {
     let result = "HelloWorld".parse::<TargetChat>().unwrap();
     dbg!(result);
 }
+
+#[test]
+#[should_panic = "5 to 32 characters"]
+fn username_too_short() {
+    let result = "@abcd".parse::<TargetChat>().unwrap();
+    dbg!(result);
+}
+
+#[test]
+#[should_panic = "5 to 32 characters"]
+fn username_too_long() {
+    let result = "@abcdefghijklmnopqrstuvwxyz0123456"
+        .parse::<TargetChat>()
+        .unwrap();
+    dbg!(result);
+}
+
+#[test]
+#[should_panic = "a-z, 0-9 and underscores"]
+fn username_invalid_characters() {
+    let result = "@hello-world".parse::<TargetChat>().unwrap();
+    dbg!(result);
+}
+
+#[test]
+#[should_panic = "start with a letter"]
+fn username_starts_with_digit() {
+    let result = "@1hello".parse::<TargetChat>().unwrap();
+    dbg!(result);
+}
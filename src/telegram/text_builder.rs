@@ -0,0 +1,304 @@
+use super::{escape, ParseMode};
+
+/// Build a text with formatting which is correctly escaped for the given [`ParseMode`].
+///
+/// All text given to the builder is treated as plain text and escaped accordingly.
+///
+/// ```
+/// use pling::{TelegramParseMode, TelegramTextBuilder};
+///
+/// let text = TelegramTextBuilder::new(TelegramParseMode::MarkdownV2)
+///     .bold("Build failed")
+///     .text(" for v1.2 in ")
+///     .code("main.rs")
+///     .build();
+/// assert_eq!(text, "*Build failed* for v1\\.2 in `main.rs`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct TextBuilder {
+    parse_mode: ParseMode,
+    text: String,
+    ends_with_italic: bool,
+}
+
+impl TextBuilder {
+    pub const fn new(parse_mode: ParseMode) -> Self {
+        Self {
+            parse_mode,
+            text: String::new(),
+            ends_with_italic: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    fn push(mut self, formatted: &str) -> Self {
+        self.text += formatted;
+        self.ends_with_italic = false;
+        self
+    }
+
+    fn wrap(self, html_tag: &str, markdown: &str, text: &str) -> Self {
+        let formatted = match self.parse_mode {
+            ParseMode::HTML => format!("<{html_tag}>{}</{html_tag}>", escape::html(text)),
+            #[allow(deprecated)]
+            ParseMode::Markdown => {
+                escape::markdown_entity(text, |run| format!("{markdown}{run}{markdown}"))
+            }
+            ParseMode::MarkdownV2 => {
+                format!("{markdown}{}{markdown}", escape::markdown_v2(text))
+            }
+        };
+        self.push(&formatted)
+    }
+
+    /// Plain text without formatting
+    pub fn text(self, text: &str) -> Self {
+        let escaped = self.parse_mode.escape(text);
+        self.push(&escaped)
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.wrap("b", "*", text)
+    }
+
+    pub fn italic(mut self, text: &str) -> Self {
+        if self.ends_with_italic && self.parse_mode == ParseMode::MarkdownV2 {
+            // Separate from the previous italic as `__` would start an underline.
+            // Telegram ignores the carriage return.
+            self.text.push('\r');
+        }
+        let mut builder = self.wrap("i", "_", text);
+        builder.ends_with_italic = true;
+        builder
+    }
+
+    /// Inline monospace text.
+    ///
+    /// The deprecated [`ParseMode::Markdown`] cannot show backticks in code, the code is interrupted around them there.
+    pub fn code(self, code: &str) -> Self {
+        let formatted = match self.parse_mode {
+            ParseMode::HTML => format!("<code>{}</code>", escape::html(code)),
+            #[allow(deprecated)]
+            ParseMode::Markdown => escape::markdown_code_entity(code, |run| format!("`{run}`")),
+            ParseMode::MarkdownV2 => format!("`{}`", escape::markdown_v2_code(code)),
+        };
+        self.push(&formatted)
+    }
+
+    /// Pre-formatted code block with an optional programming language.
+    ///
+    /// The deprecated [`ParseMode::Markdown`] cannot show backticks in code, the block is interrupted around them there.
+    pub fn pre(self, code: &str, language: Option<&str>) -> Self {
+        let formatted = match (self.parse_mode, language) {
+            (ParseMode::HTML, None) => format!("<pre>{}</pre>", escape::html(code)),
+            (ParseMode::HTML, Some(language)) => format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>",
+                escape::html(language),
+                escape::html(code)
+            ),
+            #[allow(deprecated)]
+            (ParseMode::Markdown, language) => escape::markdown_code_entity(code, |run| {
+                format!("```{}\n{run}\n```", language.unwrap_or_default())
+            }),
+            (ParseMode::MarkdownV2, language) => format!(
+                "```{}\n{}\n```",
+                language.unwrap_or_default(),
+                escape::markdown_v2_code(code)
+            ),
+        };
+        self.push(&formatted)
+    }
+
+    /// Link with the given text to the URL
+    ///
+    /// Besides `http(s)` URLs, Telegram also supports `tg://user?id=<user_id>` to mention users.
+    /// With the deprecated [`ParseMode::Markdown`] special characters in the text split the link into multiple links.
+    pub fn link(self, text: &str, url: &str) -> Self {
+        let formatted = match self.parse_mode {
+            ParseMode::HTML => format!(
+                "<a href=\"{}\">{}</a>",
+                escape::html(url),
+                escape::html(text)
+            ),
+            #[allow(deprecated)]
+            ParseMode::Markdown => escape::markdown_entity(text, |run| format!("[{run}]({url})")),
+            ParseMode::MarkdownV2 => format!(
+                "[{}]({})",
+                escape::markdown_v2(text),
+                escape::markdown_v2_url(url)
+            ),
+        };
+        self.push(&formatted)
+    }
+
+    /// Text which is hidden until tapped.
+    ///
+    /// The deprecated [`ParseMode::Markdown`] does not support spoilers, the text is added without formatting there.
+    pub fn spoiler(self, text: &str) -> Self {
+        #[allow(deprecated)]
+        let is_legacy_markdown = self.parse_mode == ParseMode::Markdown;
+        if is_legacy_markdown {
+            self.text(text)
+        } else {
+            self.wrap("tg-spoiler", "||", text)
+        }
+    }
+
+    #[must_use]
+    pub fn build(self) -> String {
+        self.text
+    }
+}
+
+#[test]
+fn html_formatting() {
+    let text = TextBuilder::new(ParseMode::HTML)
+        .bold("bold")
+        .text(" ")
+        .italic("italic")
+        .text(" ")
+        .spoiler("secret")
+        .build();
+    assert_eq!(
+        text,
+        "<b>bold</b> <i>italic</i> <tg-spoiler>secret</tg-spoiler>"
+    );
+}
+
+#[test]
+fn html_escapes_content() {
+    let text = TextBuilder::new(ParseMode::HTML)
+        .bold("a<b")
+        .text(" & ")
+        .code("Vec<u8>")
+        .build();
+    assert_eq!(text, "<b>a&lt;b</b> &amp; <code>Vec&lt;u8&gt;</code>");
+}
+
+#[test]
+fn html_pre_with_language() {
+    let text = TextBuilder::new(ParseMode::HTML)
+        .pre("if a < b {}", Some("rust"))
+        .build();
+    assert_eq!(
+        text,
+        "<pre><code class=\"language-rust\">if a &lt; b {}</code></pre>"
+    );
+}
+
+#[test]
+fn html_link() {
+    let text = TextBuilder::new(ParseMode::HTML)
+        .link("Dashboard <prod>", "https://example.com/?a=1&b=\"2\"")
+        .build();
+    assert_eq!(
+        text,
+        "<a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">Dashboard &lt;prod&gt;</a>"
+    );
+}
+
+#[test]
+fn markdown_v2_formatting() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .bold("bold!")
+        .text(" ")
+        .italic("italic.")
+        .text(" ")
+        .spoiler("secret-1")
+        .build();
+    assert_eq!(text, r"*bold\!* _italic\._ ||secret\-1||");
+}
+
+#[test]
+fn markdown_v2_code_escapes_only_code_special() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .code(r"a.b(`c`)\")
+        .build();
+    assert_eq!(text, r"`a.b(\`c\`)\\`");
+}
+
+#[test]
+fn markdown_v2_pre() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .pre("fn main() {}", Some("rust"))
+        .pre("plain", None)
+        .build();
+    assert_eq!(text, "```rust\nfn main() {}\n``````\nplain\n```");
+}
+
+#[test]
+fn markdown_v2_link() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .link(
+            "Rust (language)",
+            "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        )
+        .build();
+    assert_eq!(
+        text,
+        r"[Rust \(language\)](https://en.wikipedia.org/wiki/Rust_(programming_language\))"
+    );
+}
+
+#[test]
+fn markdown_v2_consecutive_italic_is_separated() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .italic("a")
+        .italic("b")
+        .build();
+    assert_eq!(text, "_a_\r_b_");
+}
+
+#[test]
+#[allow(deprecated)]
+fn markdown_formatting() {
+    let text = TextBuilder::new(ParseMode::Markdown)
+        .bold("snake_case")
+        .text(" ")
+        .spoiler("no *spoiler*")
+        .text(" ")
+        .link("[1]", "https://example.com/")
+        .build();
+    // Telegram shows: **snake**_**case** no *spoiler* [[1]](https://example.com/)
+    assert_eq!(
+        text,
+        r"*snake*\_*case* no \*spoiler\* \[[1](https://example.com/)]"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn markdown_code_with_backtick() {
+    let text = TextBuilder::new(ParseMode::Markdown)
+        .code("a`b")
+        .text(" ")
+        .pre("x = `y`", Some("sh"))
+        .build();
+    assert_eq!(text, "`a`\\``b` ```sh\nx = \n```\\````sh\ny\n```\\`");
+}
+
+#[test]
+fn escaped_text_survives_splitting() {
+    let text = TextBuilder::new(ParseMode::MarkdownV2)
+        .bold("Stack trace:")
+        .text("\n")
+        .pre(
+            &"at crate::module::function (src/lib.rs:1)\n".repeat(200),
+            None,
+        )
+        .build();
+    let chunks = super::LongText::Split.apply(&text, Some(ParseMode::MarkdownV2));
+    assert!(chunks.len() > 1);
+    assert!(chunks[0].starts_with("*Stack trace:*\n```\n"));
+    for chunk in &chunks[1..] {
+        assert!(chunk.starts_with("```\n"));
+    }
+    for chunk in &chunks {
+        assert!(chunk.ends_with("```"));
+    }
+}
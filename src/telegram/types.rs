@@ -0,0 +1,229 @@
+//! Objects returned by the Telegram Bot API
+//!
+//! Only the fields relevant for this crate are modelled. Unknown fields are ignored.
+
+#![allow(clippy::struct_field_names)]
+
+/// Telegram user or bot
+///
+/// Documentation: <https://core.telegram.org/bots/api#user>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct User {
+    pub id: i64,
+    pub is_bot: bool,
+    pub first_name: String,
+    pub last_name: Option<String>,
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatType {
+    Private,
+    Group,
+    Supergroup,
+    Channel,
+}
+
+impl ChatType {
+    #[must_use]
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Private => "private",
+            Self::Group => "group",
+            Self::Supergroup => "supergroup",
+            Self::Channel => "channel",
+        }
+    }
+}
+
+impl std::fmt::Display for ChatType {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.pad(self.to_str())
+    }
+}
+
+/// Documentation: <https://core.telegram.org/bots/api#chat>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Chat {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub kind: ChatType,
+    pub title: Option<String>,
+    pub username: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+impl Chat {
+    /// Title of the group or channel or the name of the user
+    #[must_use]
+    pub fn name(&self) -> String {
+        if let Some(title) = &self.title {
+            return title.clone();
+        }
+        [&self.first_name, &self.last_name]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Shows the ID, type, name and username of the chat.
+///
+/// The ID is the first column and can be used as [`TargetChat`](super::TargetChat).
+impl std::fmt::Display for Chat {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{:<16} {:<10} {}", self.id, self.kind, self.name())?;
+        if let Some(username) = &self.username {
+            write!(fmt, " @{username}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Documentation: <https://core.telegram.org/bots/api#message>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Message {
+    pub message_id: i64,
+    /// Topic of a forum supergroup the message belongs to
+    pub message_thread_id: Option<i64>,
+    pub chat: Chat,
+    pub from: Option<User>,
+    pub text: Option<String>,
+}
+
+/// Documentation: <https://core.telegram.org/bots/api#chatmemberupdated>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ChatMemberUpdated {
+    pub chat: Chat,
+    pub from: User,
+}
+
+/// Documentation: <https://core.telegram.org/bots/api#update>
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Update {
+    pub update_id: i64,
+    pub message: Option<Message>,
+    pub edited_message: Option<Message>,
+    pub channel_post: Option<Message>,
+    pub edited_channel_post: Option<Message>,
+    pub my_chat_member: Option<ChatMemberUpdated>,
+}
+
+impl Update {
+    /// The chat this update happened in
+    #[must_use]
+    pub fn chat(&self) -> Option<&Chat> {
+        self.message
+            .as_ref()
+            .or(self.edited_message.as_ref())
+            .or(self.channel_post.as_ref())
+            .or(self.edited_channel_post.as_ref())
+            .map(|message| &message.chat)
+            .or_else(|| self.my_chat_member.as_ref().map(|member| &member.chat))
+    }
+}
+
+/// Messages sent by [`Telegram`](super::Telegram)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sent {
+    /// Chat as configured in [`Telegram::target_chats`](super::Telegram::target_chats)
+    pub target_chat: super::TargetChat,
+
+    /// Multiple messages when the text was split
+    pub messages: Vec<Message>,
+
+    /// The target group was upgraded to a supergroup with this chat ID.
+    ///
+    /// The messages were sent to the supergroup instead.
+    /// Update the configuration to use the new chat ID directly.
+    pub migrated_to_chat_id: Option<i64>,
+}
+
+impl Sent {
+    #[must_use]
+    pub const fn new(target_chat: super::TargetChat) -> Self {
+        Self {
+            target_chat,
+            messages: Vec::new(),
+            migrated_to_chat_id: None,
+        }
+    }
+}
+
+/// The bot and the chats it has seen recently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenChats {
+    pub bot: User,
+    pub chats: Vec<Chat>,
+}
+
+impl SeenChats {
+    #[must_use]
+    pub(crate) fn from_updates(bot: User, updates: &[Update]) -> Self {
+        let mut chats: Vec<Chat> = Vec::new();
+        for chat in updates.iter().filter_map(Update::chat) {
+            // Keep the latest information about each chat
+            chats.retain(|existing| existing.id != chat.id);
+            chats.push(chat.clone());
+        }
+        Self { bot, chats }
+    }
+}
+
+impl std::fmt::Display for SeenChats {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let username = self.bot.username.as_deref().unwrap_or_default();
+        writeln!(fmt, "Bot: {} @{username}", self.bot.first_name)?;
+        if self.chats.is_empty() {
+            return write!(
+                fmt,
+                "No chats seen yet. Send a message to the bot or in a chat with the bot and try again."
+            );
+        }
+        for chat in &self.chats {
+            writeln!(fmt, "{chat}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn chat_name() {
+    let chat = serde_json::from_str::<Chat>(
+        r#"{"id":42,"type":"private","first_name":"Edgar","last_name":"Toll","username":"EdJoPaTo"}"#,
+    )
+    .unwrap();
+    assert_eq!(chat.name(), "Edgar Toll");
+    assert_eq!(
+        chat.to_string(),
+        "42               private    Edgar Toll @EdJoPaTo"
+    );
+}
+
+#[test]
+fn seen_chats_from_updates() {
+    let updates = serde_json::from_str::<Vec<Update>>(
+        r#"[
+            {"update_id":1,"message":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"from":{"id":42,"is_bot":false,"first_name":"Edgar"},"text":"hi"}},
+            {"update_id":2,"my_chat_member":{"chat":{"id":-100456,"type":"channel","title":"Alerts","username":"alerts"},"from":{"id":42,"is_bot":false,"first_name":"Edgar"},"date":0}},
+            {"update_id":3,"message":{"message_id":2,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops Team"},"text":"renamed"}},
+            {"update_id":4,"poll":{"id":"1"}}
+        ]"#,
+    )
+    .unwrap();
+    let bot = User {
+        id: 1,
+        is_bot: true,
+        first_name: "pling".to_owned(),
+        last_name: None,
+        username: Some("pling_bot".to_owned()),
+    };
+    let seen = SeenChats::from_updates(bot, &updates);
+    let ids = seen.chats.iter().map(|chat| chat.id).collect::<Vec<_>>();
+    assert_eq!(ids, [-100_456, -100_123]);
+    assert_eq!(seen.chats[1].name(), "Ops Team");
+}
@@ -0,0 +1,136 @@
+use super::types::{Message, Update};
+use super::TargetChat;
+
+/// Bot command like `/silence 1h` at the start of a message
+///
+/// ```
+/// use pling::TelegramCommand;
+///
+/// let command = TelegramCommand::parse("/silence@pling_bot 1h", Some("pling_bot")).unwrap();
+/// assert_eq!(command.name, "silence");
+/// assert_eq!(command.args, "1h");
+///
+/// // Commands for other bots are ignored
+/// assert_eq!(
+///     TelegramCommand::parse("/silence@other_bot 1h", Some("pling_bot")),
+///     None
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// Name of the command without the leading `/` and the bot username
+    pub name: String,
+
+    /// Text after the command with surrounding whitespace removed
+    pub args: String,
+}
+
+impl Command {
+    /// Parse the command from the start of the text.
+    ///
+    /// Commands explicitly addressed to another bot (`/ack@other_bot`) are ignored when the `bot_username` is given.
+    #[must_use]
+    pub fn parse(text: &str, bot_username: Option<&str>) -> Option<Self> {
+        let text = text.strip_prefix('/')?;
+        let (command, args) = text
+            .split_once(char::is_whitespace)
+            .map_or((text, ""), |(command, args)| (command, args.trim()));
+        let (name, username) = match command.split_once('@') {
+            Some((name, username)) => (name, Some(username)),
+            None => (command, None),
+        };
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        {
+            return None;
+        }
+        if let (Some(username), Some(bot_username)) = (username, bot_username) {
+            if !username.eq_ignore_ascii_case(bot_username) {
+                return None;
+            }
+        }
+        Some(Self {
+            name: name.to_owned(),
+            args: args.to_owned(),
+        })
+    }
+}
+
+/// Message received by the bot
+///
+/// Passed to the handler of [`Telegram::run_updates_ureq`](super::Telegram::run_updates_ureq).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incoming {
+    pub message: Message,
+
+    /// The bot command at the start of the message text
+    pub command: Option<Command>,
+
+    /// The message was sent in one of the [`Telegram::target_chats`](super::Telegram::target_chats).
+    ///
+    /// Everyone can write to a bot, so check this before reacting to commands from unknown chats.
+    pub from_target_chat: bool,
+}
+
+impl Incoming {
+    #[must_use]
+    pub(crate) fn from_update(
+        update: Update,
+        target_chats: &[TargetChat],
+        bot_username: Option<&str>,
+    ) -> Option<Self> {
+        let message = update.message.or(update.channel_post)?;
+        let command = message
+            .text
+            .as_deref()
+            .and_then(|text| Command::parse(text, bot_username));
+        let from_target_chat = target_chats
+            .iter()
+            .any(|target_chat| target_chat.is_chat(&message.chat));
+        Some(Self {
+            message,
+            command,
+            from_target_chat,
+        })
+    }
+}
+
+#[test]
+fn command_without_args() {
+    let command = Command::parse("/ack", None).unwrap();
+    assert_eq!(command.name, "ack");
+    assert_eq!(command.args, "");
+}
+
+#[test]
+fn command_with_args() {
+    let command = Command::parse("/silence   1h\n", None).unwrap();
+    assert_eq!(command.name, "silence");
+    assert_eq!(command.args, "1h");
+}
+
+#[test]
+fn command_for_this_bot() {
+    let command = Command::parse("/ack@Pling_Bot", Some("pling_bot")).unwrap();
+    assert_eq!(command.name, "ack");
+}
+
+#[test]
+fn no_command() {
+    assert_eq!(Command::parse("ack", None), None);
+    assert_eq!(Command::parse("/ ack", None), None);
+    assert_eq!(Command::parse("/path/to/file", None), None);
+}
+
+#[test]
+fn incoming_from_target_chat() {
+    let update = serde_json::from_str::<Update>(
+        r#"{"update_id":1,"message":{"message_id":1,"date":0,"chat":{"id":-100123,"type":"supergroup","title":"Ops"},"text":"/ack"}}"#,
+    )
+    .unwrap();
+    let incoming = Incoming::from_update(update, &[TargetChat::Id(-100_123)], None).unwrap();
+    assert!(incoming.from_target_chat);
+    assert_eq!(incoming.command.unwrap().name, "ack");
+}
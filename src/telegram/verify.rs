@@ -0,0 +1,151 @@
+use super::types::{Chat, ChatType, User};
+use super::TargetChat;
+
+/// Result of [`Telegram::verify_ureq`](super::Telegram::verify_ureq)
+#[derive(Debug)]
+pub struct Verification {
+    /// The bot the token belongs to
+    pub bot: User,
+
+    /// One result per [`Telegram::target_chats`](super::Telegram::target_chats) in the same order.
+    ///
+    /// Errors when the chat could not be found or the bot is not a member of it.
+    pub chats: Vec<anyhow::Result<ChatAccess>>,
+}
+
+impl Verification {
+    /// Everything is fine when all chats were found and the bot can send messages to each of them
+    #[must_use]
+    pub fn can_send_everywhere(&self) -> bool {
+        self.chats
+            .iter()
+            .all(|chat| chat.as_ref().is_ok_and(|chat| chat.can_send_messages))
+    }
+}
+
+/// What the bot can do in a target chat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatAccess {
+    /// Chat as configured in [`Telegram::target_chats`](super::Telegram::target_chats)
+    pub target_chat: TargetChat,
+
+    /// The chat with its title
+    pub chat: Chat,
+
+    /// The bot is allowed to send text messages in this chat
+    pub can_send_messages: bool,
+}
+
+/// The relevant part of the result of `getChat`
+///
+/// Documentation: <https://core.telegram.org/bots/api#chatfullinfo>
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatFullInfo {
+    #[serde(flatten)]
+    pub chat: Chat,
+    /// Default permissions of members in groups and supergroups
+    pub permissions: Option<ChatPermissions>,
+}
+
+/// Documentation: <https://core.telegram.org/bots/api#chatpermissions>
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatPermissions {
+    pub can_send_messages: Option<bool>,
+}
+
+/// The relevant part of the result of `getChatMember`
+///
+/// Documentation: <https://core.telegram.org/bots/api#chatmember>
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatMember {
+    pub status: ChatMemberStatus,
+    /// Only set for restricted members
+    pub can_send_messages: Option<bool>,
+    /// Only set for administrators of channels
+    pub can_post_messages: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatMemberStatus {
+    Creator,
+    Administrator,
+    Member,
+    Restricted,
+    Left,
+    Kicked,
+}
+
+impl ChatAccess {
+    #[must_use]
+    pub(crate) fn new(target_chat: TargetChat, info: ChatFullInfo, member: &ChatMember) -> Self {
+        let can_send_messages = match member.status {
+            ChatMemberStatus::Creator => true,
+            ChatMemberStatus::Administrator => {
+                info.chat.kind != ChatType::Channel || member.can_post_messages.unwrap_or(false)
+            }
+            ChatMemberStatus::Member => match info.chat.kind {
+                ChatType::Private => true,
+                ChatType::Channel => false,
+                ChatType::Group | ChatType::Supergroup => info
+                    .permissions
+                    .and_then(|permissions| permissions.can_send_messages)
+                    .unwrap_or(true),
+            },
+            ChatMemberStatus::Restricted => member.can_send_messages.unwrap_or(false),
+            ChatMemberStatus::Left | ChatMemberStatus::Kicked => false,
+        };
+        Self {
+            target_chat,
+            chat: info.chat,
+            can_send_messages,
+        }
+    }
+}
+
+#[cfg(test)]
+fn access(info: &str, member: &str) -> ChatAccess {
+    let info = serde_json::from_str(info).unwrap();
+    let member = serde_json::from_str(member).unwrap();
+    ChatAccess::new(TargetChat::Id(1), info, &member)
+}
+
+#[test]
+fn channel_admin_needs_post_right() {
+    let info = r#"{"id":-100456,"type":"channel","title":"Alerts"}"#;
+    let allowed = access(
+        info,
+        r#"{"status":"administrator","user":{},"can_post_messages":true}"#,
+    );
+    assert_eq!(allowed.chat.title.as_deref(), Some("Alerts"));
+    assert!(allowed.can_send_messages);
+    let forbidden = access(
+        info,
+        r#"{"status":"administrator","user":{},"can_post_messages":false}"#,
+    );
+    assert!(!forbidden.can_send_messages);
+}
+
+#[test]
+fn group_member_uses_chat_permissions() {
+    let member = r#"{"status":"member","user":{}}"#;
+    let open = access(r#"{"id":-123,"type":"group","title":"Ops"}"#, member);
+    assert!(open.can_send_messages);
+    let muted = access(
+        r#"{"id":-123,"type":"group","title":"Ops","permissions":{"can_send_messages":false}}"#,
+        member,
+    );
+    assert!(!muted.can_send_messages);
+}
+
+#[test]
+fn restricted_and_left() {
+    let info = r#"{"id":-100123,"type":"supergroup","title":"Ops"}"#;
+    let restricted = access(
+        info,
+        r#"{"status":"restricted","user":{},"can_send_messages":false}"#,
+    );
+    assert!(!restricted.can_send_messages);
+    let left = access(info, r#"{"status":"left","user":{}}"#);
+    assert!(!left.can_send_messages);
+}